@@ -1,16 +1,193 @@
-use crate::element::Element;
+use crate::element::attributes::{escape_xml, Attributes, ClassName, ElementId, Href, Transform};
+use crate::element::group::Group;
+use crate::element::layer::Layer;
+use crate::element::svg::Svg;
+use crate::element::{DepthFirstIter, Element};
+use crate::geometry::BoundingBox;
+use crate::metadata::{escape_comment, Metadata};
+use crate::namespace::{Namespace, IMPLICIT_PREFIXES};
+use crate::stylesheet::{Selector, StyleDeclarations, Stylesheet};
+use crate::visitor::{walk, walk_mut, Visitor, VisitorMut};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fmt;
+use std::fs::File;
+use std::io::Write;
+use std::ops::Range;
+use std::path::Path;
 
 static XMLNS_DEFAULT: &'static str = "http://www.w3.org/2000/svg";
 static XMLNS_XLINK_DEFAULT: &'static str = "http://www.w3.org/1999/xlink";
 
+static ID_PREFIX_DEFAULT: &str = "id_";
+
+static SVG_1_1_DOCTYPE: &str = "<!DOCTYPE svg PUBLIC \"-//W3C//DTD SVG 1.1//EN\" \"http://www.w3.org/Graphics/SVG/1.1/DTD/svg11.dtd\">";
+
+/// The XML declaration starting standalone files: `<?xml version="1.0" encoding="UTF-8" standalone="no"?>`
+#[derive(Clone, Debug)]
+pub struct XmlDeclaration {
+    /// The documents are always rendered in UTF-8,
+    /// this should only be changed if the rendered string is re-encoded
+    pub encoding: String,
+    pub standalone: Option<bool>,
+}
+
+impl Default for XmlDeclaration {
+    fn default() -> Self {
+        XmlDeclaration {
+            encoding: "UTF-8".to_string(),
+            standalone: Some(false),
+        }
+    }
+}
+
+impl fmt::Display for XmlDeclaration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "<?xml version=\"1.0\" encoding=\"{}\"{}?>",
+            self.encoding,
+            match self.standalone {
+                Some(true) => " standalone=\"yes\"",
+                Some(false) => " standalone=\"no\"",
+                None => "",
+            }
+        )
+    }
+}
+
+/// Value of the `version` attribute of the root element
+#[derive(Copy, Clone, Debug)]
+pub enum SvgVersion {
+    V1_0,
+    V1_1,
+}
+
+impl fmt::Display for SvgVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let version = match *self {
+            SvgVersion::V1_0 => "1.0",
+            SvgVersion::V1_1 => "1.1",
+        };
+        write!(f, "{}", version)
+    }
+}
+
+/// Space added around the content of a document when fitting its view box
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Padding {
+    /// The same padding in user units on every side
+    Units(f64),
+    /// Padding proportional to the content, in percent of its width on the left and right
+    /// and of its height on the top and bottom
+    Percent(f64),
+}
+
+/// How the view box of a document is computed from the bounds of its content,
+/// see `Document::fit_view_box_with`
+///
+/// # Examples
+///
+/// ```
+/// use svg_composer::document::{Padding, ViewBoxFit};
+///
+/// // Square view box with 5% of padding around the content
+/// let fit = ViewBoxFit::new()
+///     .set_padding(Padding::Percent(5.))
+///     .set_aspect_ratio(1.);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ViewBoxFit {
+    padding: Padding,
+    aspect_ratio: Option<f64>,
+    include_stroke: bool,
+}
+
+impl Default for ViewBoxFit {
+    /// No padding, no fixed aspect ratio and strokes included
+    fn default() -> Self {
+        ViewBoxFit {
+            padding: Padding::Units(0.),
+            aspect_ratio: None,
+            include_stroke: true,
+        }
+    }
+}
+
+impl ViewBoxFit {
+    pub fn new() -> Self {
+        ViewBoxFit::default()
+    }
+    pub fn set_padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+    /// Set the ratio of the width to the height of the view box, which is enlarged around the
+    /// content in one direction to reach it
+    pub fn set_aspect_ratio(mut self, ratio: f64) -> Self {
+        self.aspect_ratio = Some(ratio);
+        self
+    }
+    /// Set whether the strokes are part of the content, true by default
+    pub fn set_include_stroke(mut self, include_stroke: bool) -> Self {
+        self.include_stroke = include_stroke;
+        self
+    }
+
+    /// Returns the view box fitting the given bounds of the content
+    pub fn apply(&self, bounds: BoundingBox) -> [f32; 4] {
+        let (horizontal, vertical) = match self.padding {
+            Padding::Units(units) => (units, units),
+            Padding::Percent(percent) => (
+                bounds.width() * percent / 100.,
+                bounds.height() * percent / 100.,
+            ),
+        };
+        let mut width = bounds.width() + 2. * horizontal;
+        let mut height = bounds.height() + 2. * vertical;
+        if let Some(ratio) = self.aspect_ratio {
+            if width < height * ratio {
+                width = height * ratio;
+            } else if ratio > 0. {
+                height = width / ratio;
+            }
+        }
+        let center = bounds.center();
+        BoundingBox::new(center.0 - width / 2., center.1 - height / 2., width, height).to_view_box()
+    }
+}
+
 /// Represent an entire SVG document
 #[derive(Clone)]
 pub struct Document {
     pub xmlns: String,
     pub xmlns_xlink: String,
+    /// Additional namespaces declared on the root element, unique by prefix
+    namespaces: Vec<Namespace>,
     pub view_port: Option<[f32; 2]>,
     pub view_box: Option<[f32; 4]>,
+    /// When set, the view box is computed from the content of the document each time it is
+    /// rendered and `view_box` is only used for documents without measurable content
+    pub auto_view_box: Option<ViewBoxFit>,
+    pub version: Option<SvgVersion>,
+    /// Rendered first when set, some tools require it to recognize standalone SVG files
+    pub xml_declaration: Option<XmlDeclaration>,
+    /// Renders the SVG 1.1 DOCTYPE before the root element when true
+    pub doctype: bool,
+    /// Rendered as a `<title>` child of the root, used as the accessible name of the document
+    pub title: Option<String>,
+    /// Rendered as a `<desc>` child of the root
+    pub description: Option<String>,
+    /// Rendered as a `<metadata>` element describing the authoring and licensing of the document
+    pub metadata: Option<Metadata>,
+    /// Rendered as an XML comment before the root element, e.g. the name of the generating program
+    pub generator_comment: Option<String>,
+    /// Rendered as a `<style>` element before the elements of the document
+    pub stylesheet: Option<Stylesheet>,
     elements: Vec<Box<dyn Element>>,
+    layers: Vec<Layer>,
+    id_prefix: String,
+    id_counter: usize,
 }
 
 impl Document {
@@ -32,9 +209,22 @@ impl Document {
         Document {
             xmlns: XMLNS_DEFAULT.to_string(),
             xmlns_xlink: XMLNS_XLINK_DEFAULT.to_string(),
+            namespaces: Vec::new(),
             view_box: view_box.or(Some([0.0_f32, 0.0_f32, 100.0_f32, 100.0_f32])),
             view_port: None,
+            auto_view_box: None,
+            version: None,
+            xml_declaration: None,
+            doctype: false,
+            title: None,
+            description: None,
+            metadata: None,
+            generator_comment: None,
+            stylesheet: None,
             elements,
+            layers: Vec::new(),
+            id_prefix: ID_PREFIX_DEFAULT.to_string(),
+            id_counter: 0,
         }
     }
 
@@ -80,7 +270,586 @@ impl Document {
         self
     }
 
+    /// Insert an SVG Element at `index` in the list of elements of the Document,
+    /// shifting all elements after it. Layers are not part of this list, so `index` can be at
+    /// most `elements_len()`. Fails if it is greater.
+    pub fn insert_at(&mut self, index: usize, element: Box<dyn Element>) -> Result<&Self, String> {
+        if index > self.elements.len() {
+            return Err(format!(
+                "Index {} is out of the {} elements of the document",
+                index,
+                self.elements.len()
+            ));
+        }
+        self.elements.insert(index, element);
+        Ok(self)
+    }
+
+    /// Returns the number of elements at the top level of the Document, layers excluded
+    pub fn elements_len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns the number of elements and layers at the top level of the Document, the ones
+    /// `iter` starts from
+    pub fn len(&self) -> usize {
+        self.elements.len() + self.layers.len()
+    }
+
+    /// Returns true if the Document has no elements and no layers
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty() && self.layers.is_empty()
+    }
+
+    /// Iterates over all the elements of the Document, including children of containers and
+    /// layers, in depth-first order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svg_composer::document::Document;
+    /// use svg_composer::element::circle::Circle;
+    /// use svg_composer::element::group::Group;
+    /// use svg_composer::element::Element;
+    ///
+    /// let document = Document::new(
+    ///     vec![Box::new(Group::new().add_element(Box::new(Circle::new())))],
+    ///     None,
+    /// );
+    /// let tag_names: Vec<String> = document.iter().map(|e| e.tag_name()).collect();
+    /// assert_eq!(tag_names, vec!["g", "circle"]);
+    /// ```
+    pub fn iter(&self) -> DepthFirstIter<'_> {
+        DepthFirstIter::new(
+            self.elements
+                .iter()
+                .map(|element| element.as_ref())
+                .chain(self.layers.iter().map(|layer| layer as &dyn Element))
+                .collect(),
+        )
+    }
+
+    /// Walks all the elements of the Document, including children of containers and layers,
+    /// in depth-first order
+    pub fn accept<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        for element in self.elements.iter() {
+            walk(visitor, element.as_ref());
+        }
+        for layer in self.layers.iter() {
+            walk(visitor, layer);
+        }
+    }
+
+    /// Walks all the elements of the Document like `accept`, allowing the visitor to modify them
+    pub fn accept_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        for element in self.elements.iter_mut() {
+            walk_mut(visitor, element.as_mut());
+        }
+        for layer in self.layers.iter_mut() {
+            walk_mut(visitor, layer);
+        }
+    }
+
+    /// Returns the box containing the bounding boxes of all the elements and layers,
+    /// `None` if none of them draws anything measurable
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svg_composer::document::Document;
+    /// use svg_composer::element::circle::Circle;
+    /// use svg_composer::element::rect::Rectangle;
+    /// use svg_composer::element::Element;
+    /// use svg_composer::geometry::BoundingBox;
+    ///
+    /// let document = Document::new(
+    ///     vec![
+    ///         Box::new(Circle::new().set_pos((10., 10.)).set_radius(5.)),
+    ///         Box::new(Rectangle::new().set_pos((20., 0.)).set_size(10., 30.)),
+    ///     ],
+    ///     None,
+    /// );
+    /// assert_eq!(document.bounding_box(), Some(BoundingBox::new(5., 0., 25., 30.)));
+    /// ```
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        self.elements
+            .iter()
+            .filter_map(|x| x.bounding_box())
+            .chain(self.layers.iter().filter_map(|x| x.bounding_box()))
+            .reduce(|a, b| a.union(&b))
+    }
+
+    /// Same as `bounding_box` including the strokes, see `Element::stroke_bounding_box`
+    pub fn stroke_bounding_box(&self) -> Option<BoundingBox> {
+        self.elements
+            .iter()
+            .filter_map(|x| x.stroke_bounding_box())
+            .chain(self.layers.iter().filter_map(|x| x.stroke_bounding_box()))
+            .reduce(|a, b| a.union(&b))
+    }
+
+    /// Sets the view box to the bounds of the content including strokes, with `margin` user
+    /// units around it. Returns the new view box, or `None` and keeps the current one if the
+    /// document has no measurable content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svg_composer::document::Document;
+    /// use svg_composer::element::circle::Circle;
+    /// use svg_composer::element::Element;
+    ///
+    /// let mut document = Document::new(
+    ///     vec![Box::new(Circle::new().set_pos((250., 100.)).set_radius(40.))],
+    ///     None,
+    /// );
+    /// document.fit_view_box(10.);
+    /// assert_eq!(document.view_box, Some([200., 50., 100., 100.]));
+    /// ```
+    pub fn fit_view_box(&mut self, margin: f64) -> Option<[f32; 4]> {
+        self.fit_view_box_with(&ViewBoxFit::new().set_padding(Padding::Units(margin)))
+    }
+
+    /// Sets the view box computed from the content as described by `fit`, see `fit_view_box`
+    pub fn fit_view_box_with(&mut self, fit: &ViewBoxFit) -> Option<[f32; 4]> {
+        let view_box = self.fitted_view_box(fit)?;
+        self.view_box = Some(view_box);
+        Some(view_box)
+    }
+
+    fn fitted_view_box(&self, fit: &ViewBoxFit) -> Option<[f32; 4]> {
+        let bounds = if fit.include_stroke {
+            self.stroke_bounding_box()
+        } else {
+            self.bounding_box()
+        };
+        bounds.map(|x| fit.apply(x))
+    }
+
+    /// The view box rendered for the document, see `auto_view_box`
+    fn effective_view_box(&self) -> Option<[f32; 4]> {
+        self.auto_view_box
+            .as_ref()
+            .and_then(|fit| self.fitted_view_box(fit))
+            .or(self.view_box)
+    }
+
+    /// Returns the first element with the given id, in depth-first order
+    pub fn get_by_id(&self, id: &str) -> Option<&dyn Element> {
+        self.iter()
+            .find(|element| element.get_attributes().id.as_ref().map(ElementId::as_str) == Some(id))
+    }
+
+    /// Returns a mutable reference to the first element with the given id, in depth-first order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svg_composer::document::Document;
+    /// use svg_composer::element::circle::Circle;
+    /// use svg_composer::element::attributes::{ElementId, Size};
+    /// use svg_composer::element::Element;
+    ///
+    /// let sun = ElementId::from_string("sun".to_string()).unwrap();
+    /// let mut document = Document::new(vec![Box::new(Circle::new().set_id(sun))], None);
+    /// document.get_by_id_mut("sun").unwrap().get_mut_attributes().radius = Some(Size::from_length(5.));
+    /// ```
+    pub fn get_by_id_mut(&mut self, id: &str) -> Option<&mut dyn Element> {
+        if let Some(element) = find_by_id_mut(&mut self.elements, id) {
+            return Some(element.as_mut());
+        }
+        for layer in self.layers.iter_mut() {
+            if layer.get_attributes().id.as_ref().map(ElementId::as_str) == Some(id) {
+                return Some(layer);
+            }
+            if let Some(element) = layer.get_mut_children().and_then(|x| find_by_id_mut(x, id)) {
+                return Some(element.as_mut());
+            }
+        }
+        None
+    }
+
+    /// Removes the first element with the given id from the Document or from its container,
+    /// and returns it. Layers can only be removed with `remove_layer`.
+    pub fn remove_by_id(&mut self, id: &str) -> Option<Box<dyn Element>> {
+        remove_by_id(&mut self.elements, id).or_else(|| {
+            self.layers
+                .iter_mut()
+                .find_map(|layer| layer.get_mut_children().and_then(|x| remove_by_id(x, id)))
+        })
+    }
+
+    /// Replaces the first element with the given id by `element`, and returns the element which
+    /// was replaced
+    pub fn replace(&mut self, id: &str, element: Box<dyn Element>) -> Option<Box<dyn Element>> {
+        let mut slot = find_by_id_mut(&mut self.elements, id);
+        if slot.is_none() {
+            slot = self
+                .layers
+                .iter_mut()
+                .find_map(|layer| layer.get_mut_children().and_then(|x| find_by_id_mut(x, id)));
+        }
+        slot.map(|slot| std::mem::replace(slot, element))
+    }
+
+    /// Add an Inkscape layer on top of the existing layers of the Document.
+    /// Layers are rendered after the elements of the document, in order from bottom to top.
+    ///
+    /// # Arguments
+    ///
+    /// * `layer` - The layer to add, fails if a layer of the Document has the same label
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svg_composer::document::Document;
+    /// use svg_composer::element::layer::Layer;
+    /// use svg_composer::element::Element;
+    ///
+    /// let mut document = Document::new(Vec::<Box<dyn Element>>::new(), None);
+    /// document.add_layer(Layer::new("Pen 1")).unwrap();
+    /// document.add_layer(Layer::new("Pen 2")).unwrap();
+    /// assert!(document.add_layer(Layer::new("Pen 2")).is_err());
+    /// document.get_layer_mut("Pen 2").unwrap().hide();
+    /// document.move_layer_to_top("Pen 1").unwrap();
+    /// ```
+    pub fn add_layer(&mut self, layer: Layer) -> Result<&Self, String> {
+        if self.get_layer(layer.label()).is_some() {
+            return Err(format!("A layer is already labelled {}", layer.label()));
+        }
+        for namespace in [Namespace::inkscape(), Namespace::sodipodi()].iter() {
+            if !self.namespaces.contains(namespace) {
+                self.add_namespace(namespace.clone());
+            }
+        }
+        self.layers.push(layer);
+        Ok(self)
+    }
+
+    /// Returns the layers of the Document, from bottom to top
+    pub fn layers(&self) -> &Vec<Layer> {
+        &self.layers
+    }
+
+    pub fn get_layer(&self, label: &str) -> Option<&Layer> {
+        self.layers.iter().find(|layer| layer.label() == label)
+    }
+
+    pub fn get_layer_mut(&mut self, label: &str) -> Option<&mut Layer> {
+        self.layers.iter_mut().find(|layer| layer.label() == label)
+    }
+
+    pub fn remove_layer(&mut self, label: &str) -> Option<Layer> {
+        let index = self
+            .layers
+            .iter()
+            .position(|layer| layer.label() == label)?;
+        Some(self.layers.remove(index))
+    }
+
+    fn layer_index(&self, label: &str) -> Result<usize, String> {
+        self.layers
+            .iter()
+            .position(|layer| layer.label() == label)
+            .ok_or_else(|| format!("No layer labelled {}", label))
+    }
+
+    /// Move a layer one step up, above the layer which was directly over it
+    pub fn raise_layer(&mut self, label: &str) -> Result<(), String> {
+        let index = self.layer_index(label)?;
+        if index + 1 < self.layers.len() {
+            self.layers.swap(index, index + 1);
+        }
+        Ok(())
+    }
+
+    /// Move a layer one step down, below the layer which was directly under it
+    pub fn lower_layer(&mut self, label: &str) -> Result<(), String> {
+        let index = self.layer_index(label)?;
+        if index > 0 {
+            self.layers.swap(index, index - 1);
+        }
+        Ok(())
+    }
+
+    /// Move a layer above all other layers
+    pub fn move_layer_to_top(&mut self, label: &str) -> Result<(), String> {
+        let index = self.layer_index(label)?;
+        let layer = self.layers.remove(index);
+        self.layers.push(layer);
+        Ok(())
+    }
+
+    /// Move a layer below all other layers
+    pub fn move_layer_to_bottom(&mut self, label: &str) -> Result<(), String> {
+        let index = self.layer_index(label)?;
+        let layer = self.layers.remove(index);
+        self.layers.insert(0, layer);
+        Ok(())
+    }
+
+    /// Declare an XML namespace on the root element, replacing any namespace with the same prefix
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The namespace to declare
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svg_composer::document::Document;
+    /// use svg_composer::element::Element;
+    /// use svg_composer::namespace::Namespace;
+    ///
+    /// let mut document = Document::new(Vec::<Box<dyn Element>>::new(), None);
+    /// document.add_namespace(Namespace::inkscape());
+    /// ```
+    pub fn add_namespace(&mut self, namespace: Namespace) -> &Self {
+        self.namespaces.retain(|x| x.prefix() != namespace.prefix());
+        self.namespaces.push(namespace);
+        self
+    }
+
+    /// Returns the additional namespaces declared on the root element, see `add_namespace`
+    pub fn namespaces(&self) -> &Vec<Namespace> {
+        &self.namespaces
+    }
+
+    /// Checks that the prefix of every namespaced element and attribute of the document is
+    /// declared. Returns the list of undeclared prefixes as an error.
+    pub fn check_namespaces(&self) -> Result<(), String> {
+        let mut used_prefixes = Vec::<String>::new();
+        for element in self.elements.iter() {
+            collect_prefixes(element.as_ref(), &mut used_prefixes);
+        }
+        for layer in self.layers.iter() {
+            collect_prefixes(layer, &mut used_prefixes);
+        }
+        let undeclared_prefixes = used_prefixes
+            .into_iter()
+            .filter(|prefix| {
+                !IMPLICIT_PREFIXES.contains(&prefix.as_str())
+                    && !self.namespaces.iter().any(|x| x.prefix() == prefix)
+            })
+            .collect::<Vec<String>>();
+        if undeclared_prefixes.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Undeclared namespace prefixes: {}",
+                undeclared_prefixes.join(", ")
+            ))
+        }
+    }
+
+    /// Set the prefix of the ids generated by `new_id`, which must be a valid id by itself.
+    /// Use a distinct prefix per document when several documents are meant to be combined.
+    pub fn set_id_prefix(&mut self, prefix: &str) -> Result<(), String> {
+        ElementId::from_string(prefix.to_string())?;
+        self.id_prefix = prefix.to_string();
+        Ok(())
+    }
+
+    /// Returns a new id made of the id prefix and a counter, which is not used by any element
+    /// of the Document yet
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svg_composer::document::Document;
+    /// use svg_composer::element::circle::Circle;
+    /// use svg_composer::element::Element;
+    ///
+    /// let mut document = Document::new(Vec::<Box<dyn Element>>::new(), None);
+    /// document.set_id_prefix("chart_").unwrap();
+    /// let id = document.new_id();
+    /// assert_eq!(id.as_str(), "chart_0");
+    /// document.add_element(Box::new(Circle::new().set_id(id)));
+    /// ```
+    pub fn new_id(&mut self) -> ElementId {
+        loop {
+            let id = format!("{}{}", self.id_prefix, self.id_counter);
+            self.id_counter += 1;
+            if self.get_by_id(&id).is_none() {
+                return ElementId::from_string(id).expect("the id prefix is a valid id");
+            }
+        }
+    }
+
+    /// Checks that the ids of the Document are unique, that every local reference (`#id` links,
+    /// ids of `aria-*` id lists and `url(#id)` functions) points to an existing element and
+    /// that all namespace prefixes are declared. Returns the list of problems as an error.
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::<String>::new();
+        let mut ids = Vec::<&str>::new();
+        for element in self.iter() {
+            if let Some(id) = element.get_attributes().id.as_ref() {
+                if ids.contains(&id.as_str()) {
+                    errors.push(format!("Duplicate id \"{}\"", id));
+                } else {
+                    ids.push(id.as_str());
+                }
+            }
+        }
+        for element in self.iter() {
+            for reference in collect_references(element.get_attributes()) {
+                if !ids.contains(&reference.as_str()) {
+                    errors.push(format!(
+                        "Reference to unknown id \"{}\" from <{}>",
+                        reference,
+                        element.tag_name()
+                    ));
+                }
+            }
+        }
+        if let Err(error) = self.check_namespaces() {
+            errors.push(error);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Places the content of another Document in the target rectangle (x, y, width, height) as
+    /// a nested `<svg>` element, which maps the view box of `other` to the rectangle and clips
+    /// the content to it. See `import` for how ids, classes and styles are carried over.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svg_composer::document::Document;
+    /// use svg_composer::element::circle::Circle;
+    /// use svg_composer::element::Element;
+    ///
+    /// let panel = Document::new(vec![Box::new(Circle::new().set_pos((5., 5.)).set_radius(4.))], Some([0., 0., 10., 10.]));
+    /// let mut poster = Document::new(Vec::<Box<dyn Element>>::new(), Some([0., 0., 200., 100.]));
+    /// poster.embed(&panel, [0., 0., 100., 100.], "left_").unwrap();
+    /// poster.embed(&panel, [100., 0., 100., 100.], "right_").unwrap();
+    /// ```
+    pub fn embed(
+        &mut self,
+        other: &Document,
+        target: [f32; 4],
+        prefix: &str,
+    ) -> Result<(), String> {
+        let mut svg = Svg::new()
+            .set_pos((target[0] as f64, target[1] as f64))
+            .set_size(target[2] as f64, target[3] as f64)
+            .add_elements(self.import(other, prefix)?);
+        if let Some(view_box) = other.effective_view_box() {
+            svg = svg.set_view_box(view_box);
+        }
+        self.add_element(Box::new(import_description(other, svg)));
+        Ok(())
+    }
+
+    /// Places the content of another Document in the target rectangle (x, y, width, height) as
+    /// a group, transformed so that the view box of `other` fits in the rectangle, centered and
+    /// keeping its aspect ratio. Unlike with `embed` the content is not clipped.
+    /// See `import` for how ids, classes and styles are carried over.
+    /// Fails if the view box of `other` has no width or no height, as it cannot be scaled.
+    pub fn merge(
+        &mut self,
+        other: &Document,
+        target: [f32; 4],
+        prefix: &str,
+    ) -> Result<(), String> {
+        let [x, y, width, height] = target.map(|x| x as f64);
+        let mut transform = vec![Transform::Translate(x, y)];
+        if let Some(view_box) = other.effective_view_box() {
+            let [min_x, min_y, view_width, view_height] = view_box.map(|x| x as f64);
+            if view_width <= 0. || view_height <= 0. {
+                return Err(format!(
+                    "Cannot fit the view box of width {} and height {} in the target",
+                    view_width, view_height
+                ));
+            }
+            let scale = (width / view_width).min(height / view_height);
+            transform = vec![
+                Transform::Translate(
+                    x + (width - view_width * scale) / 2. - min_x * scale,
+                    y + (height - view_height * scale) / 2. - min_y * scale,
+                ),
+                Transform::Scale(scale, scale),
+            ];
+        }
+        let group = Group::new()
+            .set_transform(transform)
+            .add_elements(self.import(other, prefix)?);
+        self.add_element(Box::new(import_description(other, group)));
+        Ok(())
+    }
+
+    /// Returns copies of the elements and layers of another Document, with `prefix` added to
+    /// their ids, classes and to the local references to them so that they do not collide with
+    /// the ones of this Document. The prefix must be both a valid id and a valid class name.
+    /// The namespaces and the prefixed style rules of `other` are added to this Document,
+    /// rules selecting elements by tag name apply to the whole Document.
+    /// Fails if `other` binds a namespace prefix of this Document to another URI, as its
+    /// namespaced elements and attributes would change meaning.
+    fn import(&mut self, other: &Document, prefix: &str) -> Result<Vec<Box<dyn Element>>, String> {
+        ElementId::from_string(prefix.to_string())?;
+        ClassName::from_string(prefix.to_string())?;
+        for namespace in other.namespaces.iter() {
+            if let Some(declared) = self
+                .namespaces
+                .iter()
+                .find(|x| x.prefix() == namespace.prefix() && x.uri() != namespace.uri())
+            {
+                return Err(format!(
+                    "The prefix {} is already bound to {}, not {}",
+                    namespace.prefix(),
+                    declared.uri(),
+                    namespace.uri()
+                ));
+            }
+        }
+        let mut prefixer = Prefixer(prefix);
+        let mut elements = other
+            .elements
+            .iter()
+            .cloned()
+            .chain(
+                other
+                    .layers
+                    .iter()
+                    .map(|layer| Box::new(layer.clone()) as Box<dyn Element>),
+            )
+            .collect::<Vec<Box<dyn Element>>>();
+        for element in elements.iter_mut() {
+            walk_mut(&mut prefixer, element.as_mut());
+        }
+        for namespace in other.namespaces.iter() {
+            if !self
+                .namespaces
+                .iter()
+                .any(|x| x.prefix() == namespace.prefix())
+            {
+                self.namespaces.push(namespace.clone());
+            }
+        }
+        if let Some(stylesheet) = other.stylesheet.as_ref() {
+            let mut stylesheet = stylesheet.clone();
+            for (selector, declarations) in stylesheet.rules_mut().iter_mut() {
+                match selector {
+                    Selector::Id(id) => *id = prefixer.prefix_id(id),
+                    Selector::Class(class) => *class = prefixer.prefix_class(class),
+                    _ => {}
+                }
+                prefixer.prefix_declarations(declarations);
+            }
+            let rules = std::mem::take(stylesheet.rules_mut());
+            let target = self.stylesheet.get_or_insert_with(Stylesheet::new);
+            target.rules_mut().extend(rules);
+        }
+        Ok(elements)
+    }
+
     /// Renders the SVG file as a string. This string can then be saved as a regular SVG file.
+    /// Rendering does not check the document, use `validate` to find undeclared namespace
+    /// prefixes or dangling references.
     ///
     /// # Examples
     ///
@@ -96,10 +865,39 @@ impl Document {
     /// assert_eq!(expected, svg_file_content);
     /// ```
     pub fn render(&self) -> String {
-        let svg_args = vec![
-            Some(format!("xmlns=\"{}\"", self.xmlns)),
-            Some(format!("xmlns:xlink=\"{}\"", self.xmlns_xlink)),
-            self.view_box.map(|view_box| {
+        format!(
+            "{xml_declaration}{doctype}{comment}{root}",
+            xml_declaration = self
+                .xml_declaration
+                .as_ref()
+                .map(|x| format!("{}\n", x))
+                .unwrap_or_default(),
+            doctype = if self.doctype {
+                format!("{}\n", SVG_1_1_DOCTYPE)
+            } else {
+                "".to_string()
+            },
+            comment = self
+                .generator_comment
+                .as_ref()
+                .map(|x| format!("<!-- {} -->\n", escape_comment(x)))
+                .unwrap_or_default(),
+            root = self.render_root(true),
+        )
+    }
+
+    /// Renders the root `<svg>` element without the XML declaration, DOCTYPE and comment
+    fn render_root(&self, with_namespaces: bool) -> String {
+        let mut svg_args = Vec::<Option<String>>::new();
+        if with_namespaces {
+            svg_args.push(Some(format!("xmlns=\"{}\"", self.xmlns)));
+            svg_args.push(Some(format!("xmlns:xlink=\"{}\"", self.xmlns_xlink)));
+            svg_args.extend(self.namespaces.iter().map(|x| Some(x.to_string())));
+        }
+        svg_args.extend(vec![
+            self.version
+                .map(|version| format!("version=\"{}\"", version)),
+            self.effective_view_box().map(|view_box| {
                 format!(
                     "viewBox=\"{}\"",
                     view_box
@@ -119,19 +917,34 @@ impl Document {
                         .join(" ")
                 )
             }),
-        ]
-        .iter()
-        .filter(|s| s.is_some())
-        .map(|s| s.as_ref().unwrap().to_string())
-        .collect::<Vec<String>>()
-        .join(" ");
-
-        let elements = self
-            .elements
+        ]);
+        let svg_args = svg_args
             .iter()
-            .map(ToString::to_string)
+            .filter(|s| s.is_some())
+            .map(|s| s.as_ref().unwrap().to_string())
             .collect::<Vec<String>>()
-            .join("\n");
+            .join(" ");
+
+        let elements = vec![
+            self.title
+                .as_ref()
+                .map(|x| format!("<title>{}</title>", escape_xml(x))),
+            self.description
+                .as_ref()
+                .map(|x| format!("<desc>{}</desc>", escape_xml(x))),
+            self.metadata.as_ref().map(ToString::to_string),
+            self.stylesheet.as_ref().map(ToString::to_string),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(self.elements.iter().map(ToString::to_string))
+        .chain(
+            self.layers
+                .iter()
+                .map(|layer| (layer as &dyn Element).to_string()),
+        )
+        .collect::<Vec<String>>()
+        .join("\n");
 
         format!(
             "<svg {svg_args}>\n{elements}</svg>\n",
@@ -143,15 +956,802 @@ impl Document {
             }
         )
     }
+
+    /// Renders the root `<svg>` element without namespace declarations, to be embedded in an
+    /// HTML document
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svg_composer::document::Document;
+    /// use svg_composer::element::Element;
+    ///
+    /// let document = Document::new(Vec::<Box<dyn Element>>::new(), None);
+    /// assert_eq!(document.render_inline(), "<svg viewBox=\"0 0 100 100\">\n</svg>\n");
+    /// ```
+    pub fn render_inline(&self) -> String {
+        self.render_root(false)
+    }
+
+    /// Renders a complete HTML page displaying the document. The title of the document is
+    /// used as the title of the page.
+    pub fn render_html_page(&self) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n{title}</head>\n<body>\n{svg}</body>\n</html>\n",
+            title = self
+                .title
+                .as_ref()
+                .map(|x| format!("<title>{}</title>\n", escape_xml(x)))
+                .unwrap_or_default(),
+            svg = self.render_inline(),
+        )
+    }
+
+    /// Renders the document as a percent-encoded data URI wrapped in a CSS `url()`,
+    /// e.g. to be used as a `background-image`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svg_composer::document::Document;
+    /// use svg_composer::element::Element;
+    ///
+    /// let document = Document::new(Vec::<Box<dyn Element>>::new(), None);
+    /// assert!(document.render_css_url().starts_with("url(\"data:image/svg+xml,%3Csvg%20xmlns="));
+    /// ```
+    pub fn render_css_url(&self) -> String {
+        format!(
+            "url(\"data:image/svg+xml,{}\")",
+            percent_encode(&self.render_root(true))
+        )
+    }
+
+    /// Renders the document as a base64 encoded data URI, e.g. to be used as the `src` of an
+    /// HTML `<img>`
+    pub fn render_data_uri(&self) -> String {
+        format!(
+            "data:image/svg+xml;base64,{}",
+            base64::encode(self.render())
+        )
+    }
+
+    /// Renders the document and writes it to a file. Files with the `.svgz` extension are
+    /// compressed with gzip.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to create or overwrite
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use svg_composer::document::{Document, XmlDeclaration};
+    /// use svg_composer::element::Element;
+    ///
+    /// let mut document = Document::new(Vec::<Box<dyn Element>>::new(), None);
+    /// document.xml_declaration = Some(XmlDeclaration::default());
+    /// document.save("drawing.svg").unwrap();
+    /// document.save("drawing.svgz").unwrap();
+    /// ```
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let path = path.as_ref();
+        let content = self.render();
+        let mut file = File::create(path)?;
+        let is_compressed = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("svgz"));
+        if is_compressed {
+            let mut encoder = GzEncoder::new(file, Compression::default());
+            encoder.write_all(content.as_bytes())?;
+            encoder.finish()?;
+            Ok(())
+        } else {
+            file.write_all(content.as_bytes())
+        }
+    }
+}
+
+/// Copies the title and description of a Document to the element containing its content
+fn import_description<E: Element>(document: &Document, mut container: E) -> E {
+    container.get_mut_attributes().title = document.title.clone();
+    container.get_mut_attributes().desc = document.description.clone();
+    container
+}
+
+/// Finds the first element with the given id in a list of elements and their descendants
+fn find_by_id_mut<'a>(
+    elements: &'a mut Vec<Box<dyn Element>>,
+    id: &str,
+) -> Option<&'a mut Box<dyn Element>> {
+    for element in elements.iter_mut() {
+        if element.get_attributes().id.as_ref().map(ElementId::as_str) == Some(id) {
+            return Some(element);
+        }
+        if let Some(found) = element
+            .get_mut_children()
+            .and_then(|x| find_by_id_mut(x, id))
+        {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// Removes the first element with the given id from a list of elements and their descendants
+fn remove_by_id(elements: &mut Vec<Box<dyn Element>>, id: &str) -> Option<Box<dyn Element>> {
+    if let Some(index) = elements
+        .iter()
+        .position(|x| x.get_attributes().id.as_ref().map(ElementId::as_str) == Some(id))
+    {
+        return Some(elements.remove(index));
+    }
+    elements
+        .iter_mut()
+        .find_map(|element| element.get_mut_children().and_then(|x| remove_by_id(x, id)))
+}
+
+/// Attributes holding a space separated list of ids
+const ID_LIST_ATTRIBUTES: [&str; 8] = [
+    "aria-activedescendant",
+    "aria-controls",
+    "aria-describedby",
+    "aria-details",
+    "aria-errormessage",
+    "aria-flowto",
+    "aria-labelledby",
+    "aria-owns",
+];
+
+/// Collects the ids referenced by the attributes of an element
+fn collect_references(attributes: &Attributes) -> Vec<String> {
+    let mut references = Vec::<String>::new();
+    if let Some(href) = attributes.href.as_ref() {
+        let href = href.to_string();
+        references.extend(
+            attribute_reference_spans("href", &href)
+                .into_iter()
+                .map(|x| href[x].to_string()),
+        );
+    }
+    if let Some(ids) = attributes.aria_describedby.as_ref() {
+        references.extend(ids.split_whitespace().map(str::to_string));
+    }
+    for (name, value) in attributes.custom.iter() {
+        references.extend(
+            attribute_reference_spans(&name.to_string(), value)
+                .into_iter()
+                .map(|x| value[x].to_string()),
+        );
+    }
+    if let Some(style) = attributes.style.as_ref() {
+        let style = style.to_string();
+        references.extend(
+            url_reference_spans(&style)
+                .into_iter()
+                .map(|x| style[x].to_string()),
+        );
+    }
+    references
+}
+
+/// Returns the positions of the ids referenced by the value of an attribute: the value after
+/// `#` for links, every id of id lists and the ids of `url(#id)` functions otherwise
+fn attribute_reference_spans(name: &str, value: &str) -> Vec<Range<usize>> {
+    if name == "href" || name.ends_with(":href") {
+        let mut spans = Vec::<Range<usize>>::new();
+        if value.len() > 1 && value.starts_with('#') {
+            spans.push(1..value.len());
+        }
+        return spans;
+    }
+    if ID_LIST_ATTRIBUTES.contains(&name) {
+        let mut spans = Vec::<Range<usize>>::new();
+        let mut offset = 0;
+        for id in value.split_whitespace() {
+            let start = offset + value[offset..].find(id).expect("the id is in the value");
+            spans.push(start..start + id.len());
+            offset = start + id.len();
+        }
+        return spans;
+    }
+    url_reference_spans(value)
+}
+
+/// Returns the positions of the ids referenced by `url(#id)` functions in a value, the url
+/// being optionally quoted as in `url("#id")` or `url('#id')`
+fn url_reference_spans(value: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::<Range<usize>>::new();
+    let mut offset = 0;
+    while let Some(position) = value[offset..].find("url(") {
+        let argument = offset + position + 4;
+        let start = argument + value[argument..].len() - value[argument..].trim_start().len();
+        let start = match value[start..].chars().next() {
+            Some('"') | Some('\'') => start + 1,
+            _ => start,
+        };
+        offset = start;
+        if !value[start..].starts_with('#') {
+            continue;
+        }
+        let start = start + 1;
+        let end = match value[start..]
+            .find(|x: char| x == ')' || x == '"' || x == '\'' || x.is_whitespace())
+        {
+            Some(end) => start + end,
+            None => break,
+        };
+        if end > start {
+            spans.push(start..end);
+        }
+        offset = end;
+    }
+    spans
+}
+
+/// Prefixes the ids found at the given positions of a value
+fn prefix_references(value: &str, spans: Vec<Range<usize>>, prefix: &str) -> String {
+    let mut prefixed = value.to_string();
+    for span in spans.into_iter().rev() {
+        prefixed.insert_str(span.start, prefix);
+    }
+    prefixed
+}
+
+/// Prefixes the ids and classes of elements and the local references to them
+struct Prefixer<'a>(&'a str);
+
+impl Prefixer<'_> {
+    fn prefix_id(&self, id: &ElementId) -> ElementId {
+        ElementId::from_string(format!("{}{}", self.0, id)).expect("the prefix is a valid id")
+    }
+    fn prefix_class(&self, class: &ClassName) -> ClassName {
+        ClassName::from_string(format!("{}{}", self.0, class))
+            .expect("the prefix is a valid class name")
+    }
+    fn prefix_declarations(&self, declarations: &mut StyleDeclarations) {
+        declarations.map_property_values(|x| prefix_references(x, url_reference_spans(x), self.0));
+    }
+}
+
+impl VisitorMut for Prefixer<'_> {
+    fn visit_element(&mut self, element: &mut dyn Element) {
+        let attributes = element.get_mut_attributes();
+        if let Some(id) = attributes.id.as_ref() {
+            attributes.id = Some(self.prefix_id(id));
+        }
+        if let Some(classes) = attributes.class.as_mut() {
+            for class in classes.iter_mut() {
+                *class = self.prefix_class(class);
+            }
+        }
+        if let Some(href) = attributes.href.as_ref().map(ToString::to_string) {
+            let spans = attribute_reference_spans("href", &href);
+            if !spans.is_empty() {
+                attributes.href = Some(Href::from_url(&prefix_references(&href, spans, self.0)));
+            }
+        }
+        if let Some(ids) = attributes.aria_describedby.as_ref() {
+            attributes.aria_describedby = Some(
+                ids.split_whitespace()
+                    .map(|x| format!("{}{}", self.0, x))
+                    .collect::<Vec<String>>()
+                    .join(" "),
+            );
+        }
+        for (name, value) in attributes.custom.iter_mut() {
+            let spans = attribute_reference_spans(&name.to_string(), value);
+            *value = prefix_references(value, spans, self.0);
+        }
+        if let Some(style) = attributes.style.as_mut() {
+            self.prefix_declarations(style);
+        }
+    }
+}
+
+/// Percent-encodes the characters of a string which are not allowed or are ambiguous in a
+/// quoted CSS `url()` data URI
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' => encoded.push(byte as char),
+            b'-' | b'_' | b'.' | b'~' | b'!' | b'$' | b'&' | b'(' | b')' | b'*' | b'+' => {
+                encoded.push(byte as char)
+            }
+            b',' | b';' | b'=' | b':' | b'@' | b'/' | b'?' | b'\'' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Collects the namespace prefixes used by an element, its attributes and its children
+fn collect_prefixes(element: &dyn Element, prefixes: &mut Vec<String>) {
+    let tag_name = element.tag_name();
+    let attribute_prefixes = element
+        .get_attributes()
+        .custom
+        .iter()
+        .filter_map(|(name, _)| name.prefix());
+    for prefix in tag_name.split(':').rev().skip(1).chain(attribute_prefixes) {
+        if !prefixes.iter().any(|x| x == prefix) {
+            prefixes.push(prefix.to_string());
+        }
+    }
+    if let Some(children) = element.get_children() {
+        for child in children.iter() {
+            collect_prefixes(child.as_ref(), prefixes);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::element::attributes::AttributeName;
+    use crate::element::custom::CustomElement;
+
+    fn id(value: &str) -> ElementId {
+        ElementId::from_string(value.to_string()).unwrap()
+    }
 
     #[test]
     fn should_render_simple() {
         let document = Document::new(Vec::<Box<dyn Element>>::new(), None);
         assert_eq!(document.render(), "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" viewBox=\"0 0 100 100\">\n</svg>\n");
     }
+
+    #[test]
+    fn should_render_title_and_description() {
+        let mut document = Document::new(Vec::<Box<dyn Element>>::new(), None);
+        document.title = Some("Sales & costs".to_string());
+        document.description = Some("Monthly sales".to_string());
+        assert_eq!(document.render(), "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" viewBox=\"0 0 100 100\">\n<title>Sales &amp; costs</title>\n<desc>Monthly sales</desc>\n</svg>\n");
+    }
+
+    #[test]
+    fn should_declare_namespaces() {
+        let mut document = Document::new(
+            vec![Box::new(
+                CustomElement::new("sodipodi:namedview")
+                    .unwrap()
+                    .set_attribute(
+                        AttributeName::from_string("inkscape:zoom".to_string()).unwrap(),
+                        "2",
+                    )
+                    .unwrap(),
+            )],
+            None,
+        );
+        assert_eq!(
+            document.check_namespaces(),
+            Err("Undeclared namespace prefixes: sodipodi, inkscape".to_string())
+        );
+        document.add_namespace(Namespace::inkscape());
+        document.add_namespace(Namespace::inkscape());
+        document.add_namespace(Namespace::sodipodi());
+        assert_eq!(document.namespaces().len(), 2);
+        assert_eq!(document.check_namespaces(), Ok(()));
+        assert_eq!(document.render(), "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" xmlns:inkscape=\"http://www.inkscape.org/namespaces/inkscape\" xmlns:sodipodi=\"http://sodipodi.sourceforge.net/DTD/sodipodi-0.dtd\" viewBox=\"0 0 100 100\">\n<sodipodi:namedview inkscape:zoom=\"2\"/>\n</svg>\n");
+    }
+
+    #[test]
+    fn should_order_layers() {
+        let mut document = Document::new(Vec::<Box<dyn Element>>::new(), None);
+        document.add_layer(Layer::new("a")).unwrap();
+        document.add_layer(Layer::new("b")).unwrap();
+        document.add_layer(Layer::new("c")).unwrap();
+        let labels = |document: &Document| {
+            document
+                .layers()
+                .iter()
+                .map(|layer| layer.label().to_string())
+                .collect::<Vec<String>>()
+        };
+        document.raise_layer("a").unwrap();
+        assert_eq!(labels(&document), vec!["b", "a", "c"]);
+        document.lower_layer("c").unwrap();
+        assert_eq!(labels(&document), vec!["b", "c", "a"]);
+        document.move_layer_to_top("b").unwrap();
+        assert_eq!(labels(&document), vec!["c", "a", "b"]);
+        document.move_layer_to_bottom("b").unwrap();
+        assert_eq!(labels(&document), vec!["b", "c", "a"]);
+        assert!(document.raise_layer("d").is_err());
+        assert_eq!(document.check_namespaces(), Ok(()));
+    }
+
+    #[test]
+    fn should_render_generator_comment() {
+        let mut document = Document::new(Vec::<Box<dyn Element>>::new(), None);
+        document.generator_comment = Some("Generated with svg_composer".to_string());
+        assert_eq!(document.render(), "<!-- Generated with svg_composer -->\n<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" viewBox=\"0 0 100 100\">\n</svg>\n");
+    }
+
+    #[test]
+    fn should_render_standalone_header() {
+        let mut document = Document::new(Vec::<Box<dyn Element>>::new(), None);
+        document.xml_declaration = Some(XmlDeclaration::default());
+        document.doctype = true;
+        document.version = Some(SvgVersion::V1_1);
+        assert_eq!(document.render(), "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\n<!DOCTYPE svg PUBLIC \"-//W3C//DTD SVG 1.1//EN\" \"http://www.w3.org/Graphics/SVG/1.1/DTD/svg11.dtd\">\n<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" version=\"1.1\" viewBox=\"0 0 100 100\">\n</svg>\n");
+    }
+
+    #[test]
+    fn should_save_compressed_file() {
+        use flate2::read::GzDecoder;
+        use std::io::Read;
+
+        let document = Document::new(Vec::<Box<dyn Element>>::new(), None);
+        let path = std::env::temp_dir().join("svg_composer_should_save_compressed_file.svgz");
+        document.save(&path).unwrap();
+        let mut content = String::new();
+        GzDecoder::new(File::open(&path).unwrap())
+            .read_to_string(&mut content)
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(content, document.render());
+    }
+
+    #[test]
+    fn should_render_embeddable_outputs() {
+        let mut document = Document::new(Vec::<Box<dyn Element>>::new(), Some([0., 0., 10., 10.]));
+        document.title = Some("Chart".to_string());
+        document.xml_declaration = Some(XmlDeclaration::default());
+        assert_eq!(
+            document.render_html_page(),
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Chart</title>\n</head>\n<body>\n<svg viewBox=\"0 0 10 10\">\n<title>Chart</title>\n</svg>\n</body>\n</html>\n"
+        );
+        assert_eq!(
+            document.render_css_url(),
+            "url(\"data:image/svg+xml,%3Csvg%20xmlns=%22http://www.w3.org/2000/svg%22%20xmlns:xlink=%22http://www.w3.org/1999/xlink%22%20viewBox=%220%200%2010%2010%22%3E%0A%3Ctitle%3EChart%3C/title%3E%0A%3C/svg%3E%0A\")"
+        );
+        assert_eq!(
+            base64::decode(
+                document
+                    .render_data_uri()
+                    .trim_start_matches("data:image/svg+xml;base64,")
+            )
+            .unwrap(),
+            document.render().into_bytes()
+        );
+    }
+
+    #[test]
+    fn should_find_and_edit_elements_by_id() {
+        use crate::element::circle::Circle;
+        use crate::element::group::Group;
+        use crate::element::rect::Rectangle;
+
+        let mut document = Document::new(
+            vec![
+                Box::new(Circle::new().set_id(id("a"))),
+                Box::new(Group::new().set_id(id("g")).add_elements(vec![
+                    Box::new(Circle::new().set_id(id("b"))),
+                    Box::new(Circle::new().set_id(id("c"))),
+                ])),
+            ],
+            None,
+        );
+        document
+            .add_layer(Layer::new("layer").add_element(Box::new(Circle::new().set_id(id("d")))))
+            .unwrap();
+        let ids = |document: &Document| {
+            document
+                .iter()
+                .map(|x| {
+                    x.get_attributes()
+                        .id
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_default()
+                })
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(ids(&document), vec!["a", "g", "b", "c", "", "d"]);
+        assert_eq!(document.get_by_id("c").unwrap().tag_name(), "circle");
+        assert!(document.get_by_id("z").is_none());
+
+        document.get_by_id_mut("d").unwrap().get_mut_attributes().id = Some(id("e"));
+        let replaced = document.replace("b", Box::new(Rectangle::new().set_id(id("b"))));
+        assert_eq!(replaced.unwrap().tag_name(), "circle");
+        assert_eq!(document.get_by_id("b").unwrap().tag_name(), "rect");
+        assert!(document.remove_by_id("c").is_some());
+        assert!(document.remove_by_id("e").is_some());
+        document
+            .insert_at(0, Box::new(Circle::new().set_id(id("f"))))
+            .unwrap();
+        assert_eq!(ids(&document), vec!["f", "a", "g", "b", ""]);
+        assert_eq!(document.len(), 4);
+
+        let mut layered = Document::new(Vec::<Box<dyn Element>>::new(), None);
+        assert!(layered.is_empty());
+        layered.add_layer(Layer::new("layer")).unwrap();
+        assert!(!layered.is_empty() && layered.iter().count() == layered.len());
+        assert!(layered
+            .insert_at(layered.len(), Box::new(Circle::new()))
+            .is_err());
+        layered
+            .insert_at(layered.elements_len(), Box::new(Circle::new()))
+            .unwrap();
+        assert_eq!((layered.elements_len(), layered.len()), (1, 2));
+    }
+
+    #[test]
+    fn should_generate_unused_ids() {
+        use crate::element::circle::Circle;
+
+        let mut document = Document::new(vec![Box::new(Circle::new().set_id(id("id_1")))], None);
+        assert_eq!(document.new_id(), id("id_0"));
+        assert_eq!(document.new_id(), id("id_2"));
+        assert!(document.set_id_prefix("1_").is_err());
+        document.set_id_prefix("plot_").unwrap();
+        assert_eq!(document.new_id(), id("plot_3"));
+    }
+
+    #[test]
+    fn should_report_duplicate_ids_and_dangling_references() {
+        use crate::element::anchor::Anchor;
+        use crate::element::attributes::AttributeName;
+        use crate::element::circle::Circle;
+
+        let mut document = Document::new(
+            vec![
+                Box::new(Circle::new().set_id(id("a")).set_aria_describedby("a b")),
+                Box::new(
+                    Anchor::new()
+                        .set_url("#a")
+                        .add_element(Box::new(Circle::new().set_id(id("a")))),
+                ),
+                Box::new(Anchor::new().set_url("#")),
+                Box::new(
+                    Circle::new()
+                        .set_attribute(
+                            AttributeName::from_string("clip-path".to_string()).unwrap(),
+                            "url(#clip)",
+                        )
+                        .unwrap(),
+                ),
+                Box::new(
+                    Circle::new()
+                        .set_attribute(
+                            AttributeName::from_string("flood-color".to_string()).unwrap(),
+                            "#abcdef",
+                        )
+                        .unwrap()
+                        .set_attribute(
+                            AttributeName::from_string("aria-labelledby".to_string()).unwrap(),
+                            " b ",
+                        )
+                        .unwrap()
+                        .set_style(
+                            StyleDeclarations::new()
+                                .set_property("stop-color", "#00ff00")
+                                .unwrap()
+                                .set_property("mask", "url( \"#mask\" )")
+                                .unwrap(),
+                        ),
+                ),
+            ],
+            None,
+        );
+        assert_eq!(
+            document.validate(),
+            Err(vec![
+                "Duplicate id \"a\"".to_string(),
+                "Reference to unknown id \"b\" from <circle>".to_string(),
+                "Reference to unknown id \"clip\" from <circle>".to_string(),
+                "Reference to unknown id \"b\" from <circle>".to_string(),
+                "Reference to unknown id \"mask\" from <circle>".to_string(),
+            ])
+        );
+        document.remove_by_id("a");
+        document.add_element(Box::new(Circle::new().set_id(id("b"))));
+        document.add_element(Box::new(Circle::new().set_id(id("clip"))));
+        document.add_element(Box::new(Circle::new().set_id(id("mask"))));
+        assert_eq!(document.validate(), Ok(()));
+    }
+
+    #[test]
+    fn should_merge_documents_with_prefixed_ids() {
+        use crate::element::anchor::Anchor;
+        use crate::element::attributes::AttributeName;
+        use crate::element::circle::Circle;
+
+        let highlight = ClassName::from_string("highlight".to_string()).unwrap();
+        let mut panel = Document::new(
+            vec![
+                Box::new(
+                    Circle::new()
+                        .set_id(id("dot"))
+                        .set_classes(vec![highlight.clone()]),
+                ),
+                Box::new(
+                    Anchor::new()
+                        .set_url("#dot")
+                        .set_attribute(
+                            AttributeName::from_string("clip-path".to_string()).unwrap(),
+                            "url(#dot)",
+                        )
+                        .unwrap(),
+                ),
+            ],
+            Some([0., 0., 10., 20.]),
+        );
+        panel.title = Some("Panel".to_string());
+        panel.stylesheet = Some(
+            Stylesheet::new()
+                .add_rule(Selector::Class(highlight), StyleDeclarations::new())
+                .add_rule(Selector::Id(id("dot")), StyleDeclarations::new()),
+        );
+
+        let mut poster = Document::new(Vec::<Box<dyn Element>>::new(), Some([0., 0., 200., 100.]));
+        assert!(poster.embed(&panel, [0., 0., 100., 100.], "1_").is_err());
+        poster.embed(&panel, [0., 0., 100., 100.], "a_").unwrap();
+        poster.merge(&panel, [100., 0., 100., 100.], "b_").unwrap();
+        assert_eq!(poster.validate(), Ok(()));
+        assert_eq!(
+            poster.render(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" viewBox=\"0 0 200 100\">\n\
+             <style type=\"text/css\"><![CDATA[\n.a_highlight { }\n#a_dot { }\n.b_highlight { }\n#b_dot { }\n]]></style>\n\
+             <svg x=\"0\" y=\"0\" width=\"100\" height=\"100\" viewBox=\"0 0 10 20\">\n<title>Panel</title>\n\
+             <circle id=\"a_dot\" class=\"a_highlight\"/>\n\
+             <a xlink:href=\"#a_dot\" clip-path=\"url(#a_dot)\"/>\n</svg>\n\
+             <g transform=\"translate(125 0) scale(5 5)\">\n<title>Panel</title>\n\
+             <circle id=\"b_dot\" class=\"b_highlight\"/>\n\
+             <a xlink:href=\"#b_dot\" clip-path=\"url(#b_dot)\"/>\n</g>\n</svg>\n"
+        );
+
+        let mut drawing = Document::new(Vec::<Box<dyn Element>>::new(), Some([0., 0., 10., 10.]));
+        drawing.add_namespace(Namespace::new("inkscape", "http://example.com/inkscape").unwrap());
+        poster.add_namespace(Namespace::inkscape());
+        let rendered = poster.render();
+        assert!(poster.merge(&drawing, [0., 0., 10., 10.], "c_").is_err());
+        assert_eq!(poster.render(), rendered);
+    }
+
+    #[test]
+    fn should_merge_documents_without_altering_colors() {
+        use crate::element::attributes::AttributeName;
+        use crate::element::circle::Circle;
+
+        let attribute = |name: &str| AttributeName::from_string(name.to_string()).unwrap();
+        let panel = Document::new(
+            vec![Box::new(
+                Circle::new()
+                    .set_id(id("dot"))
+                    .set_attribute(attribute("flood-color"), "#abcdef")
+                    .unwrap()
+                    .set_attribute(attribute("filter"), "url('#dot')")
+                    .unwrap()
+                    .set_style(
+                        StyleDeclarations::new()
+                            .set_property("stop-color", "#00ff00")
+                            .unwrap()
+                            .set_property("mask", "url(\"#dot\")")
+                            .unwrap(),
+                    ),
+            )],
+            Some([0., 0., 10., 10.]),
+        );
+        let mut poster = Document::new(Vec::<Box<dyn Element>>::new(), Some([0., 0., 10., 10.]));
+        poster.merge(&panel, [0., 0., 10., 10.], "a_").unwrap();
+        assert_eq!(poster.validate(), Ok(()));
+        assert!(poster.render().contains(
+            "<circle id=\"a_dot\" style=\"stop-color: #00ff00; mask: url(&quot;#a_dot&quot;)\" \
+             flood-color=\"#abcdef\" filter=\"url(&apos;#a_dot&apos;)\"/>"
+        ));
+
+        let line = Document::new(Vec::<Box<dyn Element>>::new(), Some([0., 0., 10., 0.]));
+        assert!(poster.merge(&line, [0., 0., 10., 10.], "b_").is_err());
+    }
+
+    #[test]
+    fn should_compute_bounds_with_transforms_and_strokes() {
+        use crate::element::attributes::{Color, ColorName, Paint, Size, Transform};
+        use crate::element::circle::Circle;
+        use crate::element::group::Group;
+        use crate::element::line::Line;
+        use crate::element::text::Text;
+
+        let document = Document::new(
+            vec![
+                Box::new(
+                    Group::new()
+                        .set_transform(vec![
+                            Transform::Translate(100., 0.),
+                            Transform::Scale(2., 2.),
+                        ])
+                        .set_stroke(Paint::from_color(Color::from_name(ColorName::Black)))
+                        .set_stroke_width(Size::from_length(4.))
+                        .add_element(Box::new(Circle::new().set_pos((0., 0.)).set_radius(10.))),
+                ),
+                Box::new(Line::new().set_point_1((0., 50.)).set_point_2((10., 50.))),
+                Box::new(
+                    Text::new("abcde".to_string())
+                        .set_pos((0., 100.))
+                        .set_style(StyleDeclarations::new().set_font_size(10.)),
+                ),
+                Box::new(Circle::new().set_radius(Size::from_percentage(10.))),
+            ],
+            None,
+        );
+        assert_eq!(
+            document.bounding_box(),
+            Some(BoundingBox::new(0., -20., 120., 122.))
+        );
+        assert_eq!(
+            document.stroke_bounding_box(),
+            Some(BoundingBox::new(0., -24., 124., 126.))
+        );
+        assert!(Document::new(Vec::<Box<dyn Element>>::new(), None)
+            .bounding_box()
+            .is_none());
+    }
+
+    #[test]
+    fn should_compute_tight_bounds_under_rotations() {
+        use crate::element::attributes::Transform;
+        use crate::element::circle::Circle;
+        use crate::element::group::Group;
+        use crate::element::line::Line;
+
+        let document = Document::new(
+            vec![Box::new(
+                Group::new()
+                    .set_transform(vec![Transform::Rotate(45., 0., 0.)])
+                    .add_element(Box::new(Circle::new().set_radius(10.)))
+                    .add_element(Box::new(
+                        Line::new()
+                            .set_point_1((0., 0.))
+                            .set_point_2((20., 0.))
+                            .set_transform(vec![Transform::Rotate(-45., 0., 0.)]),
+                    )),
+            )],
+            None,
+        );
+        let bounding_box = document.bounding_box().unwrap();
+        for (value, expected) in [
+            (bounding_box.min_x, -10.),
+            (bounding_box.min_y, -10.),
+            (bounding_box.max_x, 20.),
+            (bounding_box.max_y, 10.),
+        ]
+        .iter()
+        {
+            assert!((value - expected).abs() < 1e-6, "{} != {}", value, expected);
+        }
+    }
+
+    #[test]
+    fn should_fit_view_box_to_content() {
+        use crate::element::attributes::{Color, ColorName, Paint, Size};
+        use crate::element::rect::Rectangle;
+
+        let mut document = Document::new(
+            vec![Box::new(
+                Rectangle::new()
+                    .set_pos((10., 20.))
+                    .set_size(40., 20.)
+                    .set_stroke(Paint::from_color(Color::from_name(ColorName::Black)))
+                    .set_stroke_width(Size::from_length(2.)),
+            )],
+            None,
+        );
+        document.auto_view_box = Some(
+            ViewBoxFit::new()
+                .set_include_stroke(false)
+                .set_padding(Padding::Percent(10.)),
+        );
+        assert!(document.render().contains("viewBox=\"6 18 48 24\""));
+        document.auto_view_box = None;
+        let fit = ViewBoxFit::new()
+            .set_padding(Padding::Units(4.))
+            .set_aspect_ratio(1.);
+        assert_eq!(document.fit_view_box_with(&fit), Some([5., 5., 50., 50.]));
+        assert_eq!(document.fit_view_box(0.), Some([9., 19., 42., 22.]));
+
+        let mut empty = Document::new(Vec::<Box<dyn Element>>::new(), None);
+        assert_eq!(empty.fit_view_box(10.), None);
+        assert_eq!(empty.view_box, Some([0., 0., 100., 100.]));
+    }
 }
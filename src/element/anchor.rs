@@ -0,0 +1,107 @@
+use crate::element::attributes::{AnchorTarget, Attributes, Href};
+use crate::element::Element;
+
+/// SVG anchor element, a container turning its children into a hyperlink
+/// https://developer.mozilla.org/en-US/docs/Web/SVG/Element/a
+///
+/// # Examples
+///
+/// ```
+/// use svg_composer::element::anchor::Anchor;
+/// use svg_composer::element::attributes::AnchorTarget;
+/// use svg_composer::element::circle::Circle;
+/// use svg_composer::element::Element;
+///
+/// let link = Anchor::new()
+///     .set_url("https://example.com/details/42")
+///     .set_target(AnchorTarget::Blank)
+///     .add_element(Box::new(Circle::new().set_radius(5.).set_title("Details")));
+/// ```
+#[derive(Clone, Default)]
+pub struct Anchor {
+    attributes: Attributes,
+    elements: Vec<Box<dyn Element>>,
+}
+
+impl Anchor {
+    pub fn new() -> Self {
+        Anchor::default()
+    }
+    pub fn set_href(mut self, href: Href) -> Self {
+        self.attributes.href = Some(href);
+        self
+    }
+    pub fn set_url(self, url: &str) -> Self {
+        self.set_href(Href::from_url(url))
+    }
+    pub fn set_target(mut self, target: AnchorTarget) -> Self {
+        self.attributes.target = Some(target);
+        self
+    }
+    pub fn add_element(mut self, element: Box<dyn Element>) -> Self {
+        self.elements.push(element);
+        self
+    }
+    pub fn add_elements(mut self, mut elements: Vec<Box<dyn Element>>) -> Self {
+        self.elements.append(&mut elements);
+        self
+    }
+    pub fn href(&self) -> Option<&Href> {
+        self.attributes.href.as_ref()
+    }
+    pub fn target(&self) -> Option<&AnchorTarget> {
+        self.attributes.target.as_ref()
+    }
+}
+
+impl Element for Anchor {
+    fn get_mut_attributes(&mut self) -> &mut Attributes {
+        &mut self.attributes
+    }
+
+    fn get_attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+
+    fn tag_name(&self) -> String {
+        "a".to_string()
+    }
+
+    fn get_children(&self) -> Option<&Vec<Box<dyn Element>>> {
+        Some(&self.elements)
+    }
+
+    fn get_mut_children(&mut self) -> Option<&mut Vec<Box<dyn Element>>> {
+        Some(&mut self.elements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::circle::Circle;
+
+    #[test]
+    fn should_render_anchor_with_children() {
+        let anchor: Box<dyn Element> = Box::new(
+            Anchor::new()
+                .set_url("/details?id=1&page=2")
+                .set_target(AnchorTarget::Blank)
+                .add_element(Box::new(
+                    Circle::new()
+                        .set_radius(5.)
+                        .set_title("Sales <2020>")
+                        .set_description("Total sales")
+                        .set_aria_describedby("legend"),
+                )),
+        );
+        assert_eq!(
+            anchor.to_string(),
+            "<a xlink:href=\"/details?id=1&amp;page=2\" target=\"_blank\">\n\
+             <circle aria-describedby=\"legend\" r=\"5\">\n\
+             <title>Sales &lt;2020&gt;</title>\n<desc>Total sales</desc>\n</circle>\n</a>"
+        );
+        let empty: Box<dyn Element> = Box::new(Anchor::new().add_element(Box::new(Circle::new())));
+        assert_eq!(empty.to_string(), "<a >\n<circle />\n</a>");
+    }
+}
@@ -1,4 +1,5 @@
 use crate::element::path::command::Commands;
+use crate::stylesheet::StyleDeclarations;
 use log::warn;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
@@ -336,6 +337,30 @@ impl Size {
     }
 }
 
+impl Size {
+    /// Returns the value in user units if the size is a length
+    pub fn as_length(&self) -> Option<f64> {
+        match self._value_type {
+            _NumberType::Length => Some(self._value),
+            _NumberType::Ratio => None,
+        }
+    }
+    /// Returns the ratio (1.0 for 100%) if the size is a percentage or a ratio
+    pub fn as_ratio(&self) -> Option<f64> {
+        match self._value_type {
+            _NumberType::Ratio => Some(self._value),
+            _NumberType::Length => None,
+        }
+    }
+    /// Formats the size as a CSS value, lengths are expressed in pixels (user units)
+    pub(crate) fn to_css(self) -> String {
+        match self._value_type {
+            _NumberType::Ratio => self.to_string(),
+            _NumberType::Length => format!("{}px", self._value),
+        }
+    }
+}
+
 impl fmt::Display for Size {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let value = match self._value_type {
@@ -380,17 +405,312 @@ impl fmt::Display for LengthAdjust {
     }
 }
 
+/// Escapes a string so it can be used as an attribute value or as text content
+pub(crate) fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// A reference to a resource, either a URL (or file path) or data embedded as a `data:` URI
+#[derive(Clone, Debug)]
+pub struct Href {
+    _value: String,
+}
+
+impl Href {
+    pub fn from_url(url: &str) -> Self {
+        Href {
+            _value: url.to_string(),
+        }
+    }
+
+    /// Embeds `data` in the document as a base64 encoded `data:` URI
+    pub fn from_data(data: &[u8], mime_type: &str) -> Self {
+        Href {
+            _value: format!("data:{};base64,{}", mime_type, base64::encode(data)),
+        }
+    }
+}
+
+impl fmt::Display for Href {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self._value)
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum AspectRatioAlign {
+    None,
+    XMinYMin,
+    XMidYMin,
+    XMaxYMin,
+    XMinYMid,
+    XMidYMid,
+    XMaxYMid,
+    XMinYMax,
+    XMidYMax,
+    XMaxYMax,
+}
+
+impl fmt::Display for AspectRatioAlign {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let align = match *self {
+            AspectRatioAlign::None => "none",
+            AspectRatioAlign::XMinYMin => "xMinYMin",
+            AspectRatioAlign::XMidYMin => "xMidYMin",
+            AspectRatioAlign::XMaxYMin => "xMaxYMin",
+            AspectRatioAlign::XMinYMid => "xMinYMid",
+            AspectRatioAlign::XMidYMid => "xMidYMid",
+            AspectRatioAlign::XMaxYMid => "xMaxYMid",
+            AspectRatioAlign::XMinYMax => "xMinYMax",
+            AspectRatioAlign::XMidYMax => "xMidYMax",
+            AspectRatioAlign::XMaxYMax => "xMaxYMax",
+        };
+        write!(f, "{}", align)
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum MeetOrSlice {
+    Meet,
+    Slice,
+}
+
+impl fmt::Display for MeetOrSlice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let meet_or_slice = match *self {
+            MeetOrSlice::Meet => "meet",
+            MeetOrSlice::Slice => "slice",
+        };
+        write!(f, "{}", meet_or_slice)
+    }
+}
+
+/// https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/preserveAspectRatio
+#[derive(Copy, Clone, Debug)]
+pub struct PreserveAspectRatio {
+    pub align: AspectRatioAlign,
+    pub meet_or_slice: Option<MeetOrSlice>,
+}
+
+impl fmt::Display for PreserveAspectRatio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.meet_or_slice {
+            Some(meet_or_slice) => write!(f, "{} {}", self.align, meet_or_slice),
+            None => write!(f, "{}", self.align),
+        }
+    }
+}
+
+/// A transformation of the coordinate system of an element and its children
+/// https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/transform
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Transform {
+    /// `matrix(a b c d e f)`, mapping (x, y) to (a * x + c * y + e, b * x + d * y + f)
+    Matrix(f64, f64, f64, f64, f64, f64),
+    Translate(f64, f64),
+    Scale(f64, f64),
+    /// Rotation by an angle in degrees around the point (cx, cy)
+    Rotate(f64, f64, f64),
+    /// Skew along the x axis by an angle in degrees
+    SkewX(f64),
+    /// Skew along the y axis by an angle in degrees
+    SkewY(f64),
+}
+
+impl fmt::Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Transform::Matrix(a, b, c, d, e, g) => {
+                write!(f, "matrix({} {} {} {} {} {})", a, b, c, d, e, g)
+            }
+            Transform::Translate(x, y) => write!(f, "translate({} {})", x, y),
+            Transform::Scale(x, y) => write!(f, "scale({} {})", x, y),
+            Transform::Rotate(angle, cx, cy) if cx == 0. && cy == 0. => {
+                write!(f, "rotate({})", angle)
+            }
+            Transform::Rotate(angle, cx, cy) => write!(f, "rotate({} {} {})", angle, cx, cy),
+            Transform::SkewX(angle) => write!(f, "skewX({})", angle),
+            Transform::SkewY(angle) => write!(f, "skewY({})", angle),
+        }
+    }
+}
+
+/// Browsing context in which a link is opened
+/// https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/target
+#[derive(Clone, Debug)]
+pub enum AnchorTarget {
+    /// `_self`: the current browsing context
+    Current,
+    Parent,
+    Top,
+    Blank,
+    /// A named browsing context such as an iframe name
+    Named(String),
+}
+
+impl fmt::Display for AnchorTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let target = match self {
+            AnchorTarget::Current => "_self",
+            AnchorTarget::Parent => "_parent",
+            AnchorTarget::Top => "_top",
+            AnchorTarget::Blank => "_blank",
+            AnchorTarget::Named(name) => name,
+        };
+        write!(f, "{}", target)
+    }
+}
+
+/// The id of an element, which must be a valid XML name without colon so that it can be
+/// referenced with `#id` from other elements and stylesheets
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElementId {
+    _value: String,
+}
+
+impl ElementId {
+    pub fn from_string(str: String) -> Result<Self, String> {
+        if str.contains(':') {
+            return Err("Ids cannot contain colons".to_string());
+        }
+        AttributeName::_is_name_valid(&str)?;
+        Ok(ElementId { _value: str })
+    }
+    pub fn as_str(&self) -> &str {
+        &self._value
+    }
+}
+
+impl fmt::Display for ElementId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self._value)
+    }
+}
+
+/// The tag name of an element, e.g. `circle`, which must be a valid XML name without colon
+#[derive(Clone, Debug, PartialEq)]
+pub struct TagName {
+    _value: String,
+}
+
+impl TagName {
+    pub fn from_string(str: String) -> Result<Self, String> {
+        if str.contains(':') {
+            return Err("Tag names cannot contain colons".to_string());
+        }
+        AttributeName::_is_name_valid(&str)?;
+        Ok(TagName { _value: str })
+    }
+    pub fn as_str(&self) -> &str {
+        &self._value
+    }
+}
+
+impl fmt::Display for TagName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self._value)
+    }
+}
+
+/// The name of an attribute which is not part of `Attributes`, optionally prefixed by a
+/// namespace, e.g. `inkscape:label`
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttributeName {
+    _value: String,
+}
+
+impl AttributeName {
+    pub fn from_string(str: String) -> Result<Self, String> {
+        let mut parts = str.split(':');
+        let (prefix, local_name) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(local_name), None, _) => (None, local_name),
+            (Some(prefix), Some(local_name), None) => (Some(prefix), local_name),
+            _ => return Err("Attribute names can contain at most one colon".to_string()),
+        };
+        if let Some(prefix) = prefix {
+            Self::_is_name_valid(prefix).map_err(|e| format!("Invalid prefix: {}", e))?;
+        }
+        Self::_is_name_valid(local_name)?;
+
+        Ok(AttributeName { _value: str })
+    }
+    /// Returns the name of a custom data attribute, `data-` followed by `name`
+    pub fn data(name: &str) -> Result<Self, String> {
+        if name.is_empty() {
+            return Err("Data attribute names cannot be empty".to_string());
+        }
+        if name.chars().any(|c| c.is_ascii_uppercase()) {
+            return Err("Data attribute names cannot contain uppercase letters".to_string());
+        }
+        if name.contains(':') {
+            return Err("Data attribute names cannot contain colons".to_string());
+        }
+        Self::from_string(format!("data-{}", name))
+    }
+    /// Returns the namespace prefix of the name, if any
+    pub fn prefix(&self) -> Option<&str> {
+        self._value.find(':').map(|i| &self._value[..i])
+    }
+    fn _is_name_valid(name: &str) -> Result<(), String> {
+        let mut chars = name.chars();
+        match chars.next() {
+            None => return Err("Empty strings do not conform with XML name standard".to_string()),
+            Some(c) if c.is_alphabetic() || c == '_' => (),
+            Some(_) => {
+                return Err("First char does not conform with XML name standard".to_string())
+            }
+        }
+        for (i, c) in chars.enumerate() {
+            if !(c.is_alphanumeric() || c == '-' || c == '_' || c == '.') {
+                return Err(format!(
+                    "Char number {} does not conform with XML name standard",
+                    i + 1
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for AttributeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self._value)
+    }
+}
+
 /// A container for attributes of any SVG element
 /// https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute
 #[derive(Default, Clone)]
 pub struct Attributes {
     // All elements
-    pub id: Option<String>,
+    pub id: Option<ElementId>,
     pub class: Option<Vec<ClassName>>,
     pub stroke: Option<Paint>,
     pub stroke_width: Option<Size>,
     pub stroke_linecap: Option<StrokeLineCap>,
     pub fill: Option<Paint>,
+    pub transform: Option<Vec<Transform>>,
+    pub style: Option<StyleDeclarations>,
+    /// Attributes which have no dedicated field, rendered in insertion order after all others
+    pub custom: Vec<(AttributeName, String)>,
+
+    // Accessibility, title and desc are rendered as children of the element
+    pub title: Option<String>,
+    pub desc: Option<String>,
+    pub role: Option<String>,
+    pub aria_label: Option<String>,
+    pub aria_describedby: Option<String>,
 
     // Path
     pub d: Option<Commands>,
@@ -420,6 +740,85 @@ pub struct Attributes {
     pub rotate_chars: Option<Vec<f64>>,
     pub dx: Option<Size>,
     pub dy: Option<Size>,
+
+    // Image
+    pub href: Option<Href>,
+    pub preserve_aspect_ratio: Option<PreserveAspectRatio>,
+
+    // Anchor
+    pub target: Option<AnchorTarget>,
+
+    // Nested svg
+    pub view_box: Option<[f32; 4]>,
+}
+
+/// Names of the attributes rendered from the typed fields of `Attributes`, which custom
+/// attributes cannot use without rendering the attribute twice
+const TYPED_ATTRIBUTE_NAMES: [&str; 34] = [
+    "id",
+    "class",
+    "style",
+    "stroke",
+    "stroke-width",
+    "stroke-linecap",
+    "fill",
+    "transform",
+    "role",
+    "aria-label",
+    "aria-describedby",
+    "d",
+    "cx",
+    "cy",
+    "r",
+    "x",
+    "y",
+    "x1",
+    "y1",
+    "x2",
+    "y2",
+    "rx",
+    "ry",
+    "dx",
+    "dy",
+    "width",
+    "height",
+    "textLength",
+    "lengthAdjust",
+    "xlink:href",
+    "viewBox",
+    "preserveAspectRatio",
+    "target",
+    "rotate",
+];
+
+impl Attributes {
+    /// Set a custom attribute, replacing any previous value of the attribute.
+    /// Fails for the names of attributes which have a typed field, like `fill` or `id`.
+    pub fn set_custom(&mut self, name: AttributeName, value: &str) -> Result<(), String> {
+        if TYPED_ATTRIBUTE_NAMES.contains(&name.to_string().as_str()) {
+            return Err(format!(
+                "The {} attribute has a typed field and cannot be set as a custom attribute",
+                name
+            ));
+        }
+        match self.custom.iter_mut().find(|(n, _)| *n == name) {
+            Some(attribute) => attribute.1 = value.to_string(),
+            None => self.custom.push((name, value.to_string())),
+        }
+        Ok(())
+    }
+    /// Returns the value of a custom attribute if it is set
+    pub fn custom(&self, name: &AttributeName) -> Option<&str> {
+        self.custom
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+    /// Remove a custom attribute, returning its value if it was set
+    pub fn remove_custom(&mut self, name: &AttributeName) -> Option<String> {
+        let index = self.custom.iter().position(|(n, _)| n == name)?;
+        Some(self.custom.remove(index).1)
+    }
 }
 
 impl fmt::Display for Attributes {
@@ -448,6 +847,24 @@ impl fmt::Display for Attributes {
             self.fill
                 .as_ref()
                 .and_then(|x| Some(format!("fill=\"{}\"", x))),
+            self.transform.as_ref().map(|x| {
+                format!(
+                    "transform=\"{}\"",
+                    x.iter()
+                        .map(|x| x.to_string())
+                        .collect::<Vec<String>>()
+                        .join(" ")
+                )
+            }),
+            self.role
+                .as_ref()
+                .map(|x| format!("role=\"{}\"", escape_xml(x))),
+            self.aria_label
+                .as_ref()
+                .map(|x| format!("aria-label=\"{}\"", escape_xml(x))),
+            self.aria_describedby
+                .as_ref()
+                .map(|x| format!("aria-describedby=\"{}\"", escape_xml(x))),
             self.d.as_ref().and_then(|x| Some(format!("d=\"{}\"", x))),
             self.cx.as_ref().and_then(|x| Some(format!("cx=\"{}\"", x))),
             self.cy.as_ref().and_then(|x| Some(format!("cy=\"{}\"", x))),
@@ -476,6 +893,24 @@ impl fmt::Display for Attributes {
             self.length_adjust
                 .as_ref()
                 .and_then(|x| Some(format!("lengthAdjust=\"{}\"", x))),
+            self.href
+                .as_ref()
+                .map(|x| format!("xlink:href=\"{}\"", escape_xml(&x.to_string()))),
+            self.view_box.as_ref().map(|x| {
+                format!(
+                    "viewBox=\"{}\"",
+                    x.iter()
+                        .map(|x| x.to_string())
+                        .collect::<Vec<String>>()
+                        .join(" ")
+                )
+            }),
+            self.preserve_aspect_ratio
+                .as_ref()
+                .map(|x| format!("preserveAspectRatio=\"{}\"", x)),
+            self.target
+                .as_ref()
+                .map(|x| format!("target=\"{}\"", escape_xml(&x.to_string()))),
             self.rotate_chars.as_ref().and_then(|x| {
                 Some(format!(
                     "rotate=\"{}\"",
@@ -489,8 +924,94 @@ impl fmt::Display for Attributes {
         .iter()
         .filter(|x| x.is_some())
         .map(|x| x.clone().unwrap())
+        .chain(self.style.iter().filter(|x| !x.is_empty()).map(|x| {
+            format!("style=\"{}\"", escape_xml(&x.to_string()))
+        }))
+        .chain(
+            self.custom
+                .iter()
+                .map(|(name, value)| format!("{}=\"{}\"", name, escape_xml(value))),
+        )
         .collect::<Vec<String>>()
         .join(" ");
         write!(f, "{}", formatted_attributes)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_validate_attribute_names() {
+        assert!(AttributeName::from_string("inkscape:label".to_string()).is_ok());
+        assert!(AttributeName::from_string("a:b:c".to_string()).is_err());
+        assert!(AttributeName::from_string("1abc".to_string()).is_err());
+        assert!(AttributeName::from_string("on click".to_string()).is_err());
+        assert!(AttributeName::data("user-id").is_ok());
+        assert!(AttributeName::data("userId").is_err());
+        assert!(AttributeName::data("").is_err());
+        assert_eq!(
+            AttributeName::from_string("inkscape:label".to_string())
+                .unwrap()
+                .prefix(),
+            Some("inkscape")
+        );
+    }
+
+    #[test]
+    fn should_render_transforms() {
+        let transforms = [
+            Transform::Translate(10., -5.),
+            Transform::Rotate(45., 0., 0.),
+            Transform::Rotate(90., 1., 2.),
+            Transform::Matrix(1., 0., 0., 1., 2.5, 0.),
+        ];
+        assert_eq!(
+            transforms
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "translate(10 -5)",
+                "rotate(45)",
+                "rotate(90 1 2)",
+                "matrix(1 0 0 1 2.5 0)"
+            ]
+        );
+    }
+
+    #[test]
+    fn should_validate_ids() {
+        assert!(ElementId::from_string("panel_1.path-2".to_string()).is_ok());
+        assert!(ElementId::from_string("".to_string()).is_err());
+        assert!(ElementId::from_string("2d".to_string()).is_err());
+        assert!(ElementId::from_string("a:b".to_string()).is_err());
+        assert!(ElementId::from_string("a b".to_string()).is_err());
+    }
+
+    #[test]
+    fn should_render_custom_attributes_in_order() {
+        let mut attributes = Attributes {
+            style: Some(StyleDeclarations::new().set_opacity(0.5)),
+            ..Attributes::default()
+        };
+        attributes
+            .set_custom(AttributeName::data("b").unwrap(), "1")
+            .unwrap();
+        attributes
+            .set_custom(AttributeName::data("a").unwrap(), "\"quoted\"")
+            .unwrap();
+        attributes
+            .set_custom(AttributeName::data("b").unwrap(), "2")
+            .unwrap();
+        for name in ["fill", "id", "xlink:href", "viewBox"].iter() {
+            let name = AttributeName::from_string(name.to_string()).unwrap();
+            assert!(attributes.set_custom(name, "x").is_err());
+        }
+        assert_eq!(
+            attributes.to_string(),
+            "style=\"opacity: 0.5\" data-b=\"2\" data-a=\"&quot;quoted&quot;\""
+        );
+    }
+}
@@ -1,5 +1,8 @@
 use crate::element::attributes::{Attributes, Size, ToSize};
-use crate::element::Element;
+use crate::element::path::builder::PathBuilder;
+use crate::element::path::{Commands, Path, ToPath};
+use crate::element::{length_or, shape_bounding_box, Element};
+use crate::geometry::{BoundingBox, Matrix};
 
 #[derive(Clone)]
 pub struct Circle {
@@ -27,6 +30,70 @@ impl Circle {
         self.attributes.radius = Some(radius.to_size());
         self
     }
+    pub fn pos(&self) -> Option<(Size, Size)> {
+        self.attributes.cx.zip(self.attributes.cy)
+    }
+    pub fn radius(&self) -> Option<Size> {
+        self.attributes.radius
+    }
+    /// Returns the center and the radius in user units, `None` if a size is a percentage
+    fn geometry(&self) -> Option<((f64, f64), f64)> {
+        Some((
+            (
+                length_or(self.attributes.cx, 0.)?,
+                length_or(self.attributes.cy, 0.)?,
+            ),
+            length_or(self.attributes.radius, 0.)?,
+        ))
+    }
+    /// Returns the commands drawing the circle with two arcs, clockwise from its rightmost
+    /// point. Returns `None` if a size is a percentage.
+    pub(crate) fn to_commands(&self) -> Option<Commands> {
+        let ((cx, cy), radius) = self.geometry()?;
+        if radius <= 0. {
+            return Some(Commands::new());
+        }
+        let radii = (radius, radius);
+        Some(
+            PathBuilder::new()
+                .move_to((cx + radius, cy))
+                .arc_to(radii, 0., false, true, (cx - radius, cy))
+                .arc_to(radii, 0., false, true, (cx + radius, cy))
+                .close()
+                .build(),
+        )
+    }
+    /// Applies the transform of the circle to its geometry and removes it.
+    /// The circle is kept if the transform maps it to a circle, it is converted to a path
+    /// otherwise, hence the boxed result. Lengths which are not coordinates, like the stroke
+    /// width, are left unchanged. Fails if a size of the circle is a percentage.
+    pub fn bake_transform(mut self) -> Result<Box<dyn Element>, String> {
+        let transforms = match self.attributes.transform.take() {
+            Some(transforms) => transforms,
+            None => return Ok(Box::new(self)),
+        };
+        let matrix = Matrix::from_transforms(&transforms);
+        let (center, radius) = self
+            .geometry()
+            .ok_or("Circles with percentage sizes cannot be transformed")?;
+        if matrix.is_similarity() {
+            return Ok(Box::new(
+                self.set_pos(matrix.apply(center))
+                    .set_radius(radius * matrix.determinant().abs().sqrt()),
+            ));
+        }
+        let commands = self.to_commands().unwrap_or_default().transform(&matrix);
+        Ok(Box::new(Path::from_shape(self.attributes, commands)))
+    }
+}
+
+impl ToPath for Circle {
+    fn to_path(&self) -> Result<Path, String> {
+        let commands = self
+            .to_commands()
+            .ok_or("Circles with percentage sizes cannot be converted to paths")?;
+        Ok(Path::from_shape(self.attributes.clone(), commands))
+    }
 }
 
 impl Element for Circle {
@@ -41,4 +108,20 @@ impl Element for Circle {
     fn tag_name(&self) -> String {
         "circle".to_string()
     }
+
+    fn local_bounding_box(&self) -> Option<BoundingBox> {
+        let cx = length_or(self.attributes.cx, 0.)?;
+        let cy = length_or(self.attributes.cy, 0.)?;
+        let radius = length_or(self.attributes.radius, 0.)?;
+        Some(BoundingBox::new(
+            cx - radius,
+            cy - radius,
+            2. * radius,
+            2. * radius,
+        ))
+    }
+
+    fn transformed_bounding_box(&self, matrix: &Matrix) -> Option<BoundingBox> {
+        shape_bounding_box(self.to_commands(), self.local_bounding_box(), matrix)
+    }
 }
@@ -0,0 +1,78 @@
+use crate::element::attributes::{escape_xml, AttributeName, Attributes};
+use crate::element::Element;
+
+/// An element which is not part of the SVG specification, typically from another namespace
+/// such as `sodipodi:namedview`. The prefix of the tag name must be declared on the document.
+///
+/// # Examples
+///
+/// ```
+/// use svg_composer::element::attributes::AttributeName;
+/// use svg_composer::element::custom::CustomElement;
+/// use svg_composer::element::Element;
+///
+/// let named_view = CustomElement::new("sodipodi:namedview")
+///     .unwrap()
+///     .set_attribute(
+///         AttributeName::from_string("inkscape:zoom".to_string()).unwrap(),
+///         "2",
+///     )
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct CustomElement {
+    tag_name: AttributeName,
+    attributes: Attributes,
+    text: Option<String>,
+    elements: Vec<Box<dyn Element>>,
+}
+
+impl CustomElement {
+    pub fn new(tag_name: &str) -> Result<Self, String> {
+        Ok(CustomElement {
+            tag_name: AttributeName::from_string(tag_name.to_string())?,
+            attributes: Attributes::default(),
+            text: None,
+            elements: Vec::new(),
+        })
+    }
+    /// Set the text content of the element, rendered before its children
+    pub fn set_text(mut self, text: &str) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+    pub fn add_element(mut self, element: Box<dyn Element>) -> Self {
+        self.elements.push(element);
+        self
+    }
+    pub fn add_elements(mut self, mut elements: Vec<Box<dyn Element>>) -> Self {
+        self.elements.append(&mut elements);
+        self
+    }
+}
+
+impl Element for CustomElement {
+    fn get_mut_attributes(&mut self) -> &mut Attributes {
+        &mut self.attributes
+    }
+
+    fn get_attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+
+    fn tag_name(&self) -> String {
+        self.tag_name.to_string()
+    }
+
+    fn tag_content(&self) -> Option<String> {
+        self.text.as_ref().map(|x| escape_xml(x))
+    }
+
+    fn get_children(&self) -> Option<&Vec<Box<dyn Element>>> {
+        Some(&self.elements)
+    }
+
+    fn get_mut_children(&mut self) -> Option<&mut Vec<Box<dyn Element>>> {
+        Some(&mut self.elements)
+    }
+}
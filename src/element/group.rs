@@ -0,0 +1,62 @@
+use crate::element::attributes::Attributes;
+use crate::element::Element;
+
+/// SVG group element, a container applying its attributes to all of its children
+/// https://developer.mozilla.org/en-US/docs/Web/SVG/Element/g
+///
+/// # Examples
+///
+/// ```
+/// use svg_composer::element::attributes::{Color, ColorName, Paint};
+/// use svg_composer::element::circle::Circle;
+/// use svg_composer::element::group::Group;
+/// use svg_composer::element::Element;
+///
+/// let group = Group::new()
+///     .set_fill(Paint::from_color(Color::from_name(ColorName::Red)))
+///     .add_elements(vec![
+///         Box::new(Circle::new().set_radius(5.)),
+///         Box::new(Circle::new().set_radius(10.)),
+///     ]);
+/// ```
+#[derive(Clone, Default)]
+pub struct Group {
+    attributes: Attributes,
+    elements: Vec<Box<dyn Element>>,
+}
+
+impl Group {
+    pub fn new() -> Self {
+        Group::default()
+    }
+    pub fn add_element(mut self, element: Box<dyn Element>) -> Self {
+        self.elements.push(element);
+        self
+    }
+    pub fn add_elements(mut self, mut elements: Vec<Box<dyn Element>>) -> Self {
+        self.elements.append(&mut elements);
+        self
+    }
+}
+
+impl Element for Group {
+    fn get_mut_attributes(&mut self) -> &mut Attributes {
+        &mut self.attributes
+    }
+
+    fn get_attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+
+    fn tag_name(&self) -> String {
+        "g".to_string()
+    }
+
+    fn get_children(&self) -> Option<&Vec<Box<dyn Element>>> {
+        Some(&self.elements)
+    }
+
+    fn get_mut_children(&mut self) -> Option<&mut Vec<Box<dyn Element>>> {
+        Some(&mut self.elements)
+    }
+}
@@ -0,0 +1,174 @@
+use crate::element::attributes::{Attributes, Href, PreserveAspectRatio, Size, ToSize};
+use crate::element::{length_or, Element};
+use crate::geometry::BoundingBox;
+
+/// Raster image formats which can be embedded in a document
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+}
+
+impl ImageFormat {
+    /// Detects the format of an image from the magic number at the start of its content
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        if data.starts_with(&[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n']) {
+            Some(ImageFormat::Png)
+        } else if data.starts_with(&[0xff, 0xd8, 0xff]) {
+            Some(ImageFormat::Jpeg)
+        } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+            Some(ImageFormat::Gif)
+        } else if data.len() >= 12 && data.starts_with(b"RIFF") && &data[8..12] == b"WEBP" {
+            Some(ImageFormat::Webp)
+        } else {
+            None
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match *self {
+            ImageFormat::Png => "image/png",
+            ImageFormat::Jpeg => "image/jpeg",
+            ImageFormat::Gif => "image/gif",
+            ImageFormat::Webp => "image/webp",
+        }
+    }
+}
+
+/// SVG image element
+/// https://developer.mozilla.org/en-US/docs/Web/SVG/Element/image
+///
+/// # Examples
+///
+/// ```
+/// use svg_composer::element::image::Image;
+///
+/// let png_header = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+/// let embedded = Image::new()
+///     .set_pos((0., 0.))
+///     .set_size(64., 64.)
+///     .set_data(&png_header)
+///     .unwrap();
+/// let linked = Image::new().set_url("thumbnails/photo_1.jpg");
+/// ```
+#[derive(Clone, Default)]
+pub struct Image {
+    attributes: Attributes,
+}
+
+impl Image {
+    pub fn new() -> Self {
+        Image::default()
+    }
+    pub fn set_pos<I>(mut self, pos: (I, I)) -> Self
+    where
+        I: ToSize,
+    {
+        self.attributes.x = Some(pos.0.to_size());
+        self.attributes.y = Some(pos.1.to_size());
+        self
+    }
+    pub fn set_size<I>(mut self, width: I, height: I) -> Self
+    where
+        I: ToSize,
+    {
+        self.attributes.width = Some(width.to_size());
+        self.attributes.height = Some(height.to_size());
+        self
+    }
+    pub fn set_preserve_aspect_ratio(mut self, value: PreserveAspectRatio) -> Self {
+        self.attributes.preserve_aspect_ratio = Some(value);
+        self
+    }
+    pub fn set_href(mut self, href: Href) -> Self {
+        self.attributes.href = Some(href);
+        self
+    }
+    /// Links to an external image using a URL or a file path
+    pub fn set_url(self, url: &str) -> Self {
+        self.set_href(Href::from_url(url))
+    }
+    /// Embeds the image content in the document as a `data:` URI.
+    /// Fails if the content is not a PNG, JPEG, GIF or WebP image.
+    pub fn set_data(self, data: &[u8]) -> Result<Self, String> {
+        let format = ImageFormat::from_bytes(data)
+            .ok_or_else(|| "Image data is not a PNG, JPEG, GIF or WebP image".to_string())?;
+        Ok(self.set_href(Href::from_data(data, format.mime_type())))
+    }
+    pub fn pos(&self) -> Option<(Size, Size)> {
+        self.attributes.x.zip(self.attributes.y)
+    }
+    pub fn size(&self) -> Option<(Size, Size)> {
+        self.attributes.width.zip(self.attributes.height)
+    }
+    pub fn href(&self) -> Option<&Href> {
+        self.attributes.href.as_ref()
+    }
+}
+
+impl Element for Image {
+    fn get_mut_attributes(&mut self) -> &mut Attributes {
+        &mut self.attributes
+    }
+
+    fn get_attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+
+    fn tag_name(&self) -> String {
+        "image".to_string()
+    }
+
+    fn local_bounding_box(&self) -> Option<BoundingBox> {
+        Some(BoundingBox::new(
+            length_or(self.attributes.x, 0.)?,
+            length_or(self.attributes.y, 0.)?,
+            length_or(self.attributes.width, 0.)?,
+            length_or(self.attributes.height, 0.)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_detect_image_format() {
+        assert_eq!(
+            ImageFormat::from_bytes(&[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n', 0]),
+            Some(ImageFormat::Png)
+        );
+        assert_eq!(
+            ImageFormat::from_bytes(&[0xff, 0xd8, 0xff, 0xe0]),
+            Some(ImageFormat::Jpeg)
+        );
+        assert_eq!(ImageFormat::from_bytes(b"GIF89a..."), Some(ImageFormat::Gif));
+        assert_eq!(
+            ImageFormat::from_bytes(b"RIFF\x10\0\0\0WEBPVP8 "),
+            Some(ImageFormat::Webp)
+        );
+        assert_eq!(ImageFormat::from_bytes(b"<svg></svg>"), None);
+    }
+
+    #[test]
+    fn should_render_embedded_image() {
+        let image: Box<dyn Element> = Box::new(
+            Image::new()
+                .set_size(10., 20.)
+                .set_data(b"GIF89a")
+                .unwrap(),
+        );
+        assert_eq!(
+            image.to_string(),
+            "<image width=\"10\" height=\"20\" xlink:href=\"data:image/gif;base64,R0lGODlh\"/>"
+        );
+    }
+
+    #[test]
+    fn should_reject_unknown_image_data() {
+        assert!(Image::new().set_data(b"not an image").is_err());
+    }
+}
@@ -0,0 +1,166 @@
+use crate::element::attributes::{AttributeName, Attributes};
+use crate::element::Element;
+
+/// An Inkscape layer, a group rendered as `<g inkscape:groupmode="layer" inkscape:label="...">`.
+/// Layers are added to a document with `Document::add_layer`, which declares the `inkscape`
+/// and `sodipodi` namespaces they rely on. The label, visibility and lock of a layer are read
+/// from its attributes, so they stay in sync when the attributes are edited directly.
+///
+/// # Examples
+///
+/// ```
+/// use svg_composer::element::circle::Circle;
+/// use svg_composer::element::layer::Layer;
+///
+/// let layer = Layer::new("Pen 1")
+///     .set_locked(true)
+///     .add_element(Box::new(Circle::new().set_radius(5.)));
+/// ```
+#[derive(Clone)]
+pub struct Layer {
+    attributes: Attributes,
+    elements: Vec<Box<dyn Element>>,
+}
+
+impl Layer {
+    pub fn new(label: &str) -> Self {
+        let mut attributes = Attributes::default();
+        Self::set_custom(&mut attributes, "inkscape:groupmode", "layer");
+        Self::set_custom(&mut attributes, "inkscape:label", label);
+        Layer {
+            attributes,
+            elements: Vec::new(),
+        }
+    }
+    fn attribute_name(name: &str) -> AttributeName {
+        AttributeName::from_string(name.to_string()).unwrap()
+    }
+    fn set_custom(attributes: &mut Attributes, name: &str, value: &str) {
+        attributes
+            .set_custom(Self::attribute_name(name), value)
+            .expect("layer attributes have no typed field");
+    }
+    pub fn label(&self) -> &str {
+        self.attributes
+            .custom(&Self::attribute_name("inkscape:label"))
+            .unwrap_or_default()
+    }
+    pub fn is_visible(&self) -> bool {
+        self.attributes
+            .style
+            .as_ref()
+            .and_then(|x| x.property("display"))
+            != Some("none")
+    }
+    pub fn is_locked(&self) -> bool {
+        self.attributes
+            .custom(&Self::attribute_name("sodipodi:insensitive"))
+            == Some("true")
+    }
+    /// Hidden layers are not displayed but are kept in the document
+    pub fn set_visible(mut self, visible: bool) -> Self {
+        if visible {
+            self.show();
+        } else {
+            self.hide();
+        }
+        self
+    }
+    /// Locked layers cannot be selected or edited in Inkscape
+    pub fn set_locked(mut self, locked: bool) -> Self {
+        if locked {
+            self.lock();
+        } else {
+            self.unlock();
+        }
+        self
+    }
+    pub fn show(&mut self) {
+        self.set_display("inline");
+    }
+    pub fn hide(&mut self) {
+        self.set_display("none");
+    }
+    fn set_display(&mut self, value: &str) {
+        self.attributes.style = Some(
+            self.attributes
+                .style
+                .take()
+                .unwrap_or_default()
+                .set_property("display", value)
+                .expect("display values are valid"),
+        );
+    }
+    pub fn lock(&mut self) {
+        Self::set_custom(&mut self.attributes, "sodipodi:insensitive", "true");
+    }
+    pub fn unlock(&mut self) {
+        self.attributes
+            .remove_custom(&Self::attribute_name("sodipodi:insensitive"));
+    }
+    pub fn add_element(mut self, element: Box<dyn Element>) -> Self {
+        self.elements.push(element);
+        self
+    }
+    pub fn add_elements(mut self, mut elements: Vec<Box<dyn Element>>) -> Self {
+        self.elements.append(&mut elements);
+        self
+    }
+}
+
+impl Element for Layer {
+    fn get_mut_attributes(&mut self) -> &mut Attributes {
+        &mut self.attributes
+    }
+
+    fn get_attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+
+    fn tag_name(&self) -> String {
+        "g".to_string()
+    }
+
+    fn get_children(&self) -> Option<&Vec<Box<dyn Element>>> {
+        Some(&self.elements)
+    }
+
+    fn get_mut_children(&mut self) -> Option<&mut Vec<Box<dyn Element>>> {
+        Some(&mut self.elements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_render_layer() {
+        let layer: Box<dyn Element> =
+            Box::new(Layer::new("Pen \"1\"").set_visible(false).set_locked(true));
+        assert_eq!(
+            layer.to_string(),
+            "<g style=\"display: none\" inkscape:groupmode=\"layer\" \
+             inkscape:label=\"Pen &quot;1&quot;\" sodipodi:insensitive=\"true\"/>"
+        );
+    }
+
+    #[test]
+    fn should_read_state_from_attributes() {
+        let mut layer = Layer::new("Pen 1").set_locked(true);
+        assert!(layer.is_visible() && layer.is_locked());
+        layer.hide();
+        layer.unlock();
+        assert!(!layer.is_visible() && !layer.is_locked());
+        layer
+            .get_mut_attributes()
+            .set_custom(
+                AttributeName::from_string("inkscape:label".to_string()).unwrap(),
+                "Pen 2",
+            )
+            .unwrap();
+        layer.get_mut_attributes().style = None;
+        assert_eq!(layer.label(), "Pen 2");
+        assert!(layer.is_visible());
+    }
+}
@@ -1,5 +1,8 @@
 use crate::element::attributes::{Attributes, Size, ToSize};
-use crate::element::Element;
+use crate::element::path::builder::PathBuilder;
+use crate::element::path::{Path, ToPath};
+use crate::element::{length_or, Element};
+use crate::geometry::{BoundingBox, Matrix};
 
 #[derive(Clone)]
 pub struct Line {
@@ -28,6 +31,54 @@ impl Line {
         self.attributes.y2 = Some(p.1.to_size());
         self
     }
+    pub fn point_1(&self) -> Option<(Size, Size)> {
+        self.attributes.x1.zip(self.attributes.y1)
+    }
+    pub fn point_2(&self) -> Option<(Size, Size)> {
+        self.attributes.x2.zip(self.attributes.y2)
+    }
+    /// Returns the end points in user units, `None` if a coordinate is a percentage
+    fn points(&self) -> Option<((f64, f64), (f64, f64))> {
+        Some((
+            (
+                length_or(self.attributes.x1, 0.)?,
+                length_or(self.attributes.y1, 0.)?,
+            ),
+            (
+                length_or(self.attributes.x2, 0.)?,
+                length_or(self.attributes.y2, 0.)?,
+            ),
+        ))
+    }
+    /// Applies the transform of the line to its end points and removes it.
+    /// Unlike circles and rectangles, lines stay lines under any transform so a line is
+    /// returned. Lengths which are not coordinates, like the stroke width, are left unchanged.
+    /// Fails if a coordinate of the line is a percentage.
+    pub fn bake_transform(mut self) -> Result<Line, String> {
+        let transforms = match self.attributes.transform.take() {
+            Some(transforms) => transforms,
+            None => return Ok(self),
+        };
+        let matrix = Matrix::from_transforms(&transforms);
+        let points = self
+            .points()
+            .ok_or("Lines with percentage coordinates cannot be transformed")?;
+        Ok(self
+            .set_point_1(matrix.apply(points.0))
+            .set_point_2(matrix.apply(points.1)))
+    }
+}
+
+impl ToPath for Line {
+    fn to_path(&self) -> Result<Path, String> {
+        let (point_1, point_2) = self
+            .points()
+            .ok_or("Lines with percentage coordinates cannot be converted to paths")?;
+        Ok(Path::from_shape(
+            self.attributes.clone(),
+            PathBuilder::new().move_to(point_1).line_to(point_2).build(),
+        ))
+    }
 }
 
 impl Element for Line {
@@ -42,4 +93,14 @@ impl Element for Line {
     fn tag_name(&self) -> String {
         "line".to_string()
     }
+
+    fn local_bounding_box(&self) -> Option<BoundingBox> {
+        let (point_1, point_2) = self.points()?;
+        BoundingBox::from_points(vec![point_1, point_2])
+    }
+
+    fn transformed_bounding_box(&self, matrix: &Matrix) -> Option<BoundingBox> {
+        let (point_1, point_2) = self.points()?;
+        BoundingBox::from_points(vec![matrix.apply(point_1), matrix.apply(point_2)])
+    }
 }
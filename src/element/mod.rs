@@ -1,23 +1,53 @@
-use crate::element::attributes::{Attributes, ClassName, Paint, Size, StrokeLineCap};
+use crate::element::attributes::{
+    escape_xml, AttributeName, Attributes, ClassName, ElementId, Paint, Size, StrokeLineCap,
+    Transform,
+};
+use crate::element::path::Commands;
+use crate::element::svg::Svg;
+use crate::geometry::{BoundingBox, Matrix};
+use crate::stylesheet::StyleDeclarations;
 use std::collections::HashMap;
 
 use crate::element::path::command::End;
 use dyn_clone::DynClone;
 pub use path::Path;
+use std::any::Any;
 use std::fmt;
 use std::fmt::Formatter;
 
+pub mod anchor;
 pub mod attributes;
 pub mod circle;
+pub mod custom;
+pub mod group;
+pub mod image;
+pub mod layer;
 pub mod line;
 pub mod path;
 pub mod rect;
+pub mod svg;
 pub mod text;
 
+/// Conversion to `Any`, implemented for every element so that boxed elements can be downcast
+/// to their concrete type with `downcast_ref` and `downcast_mut`
+pub trait AsAny {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
 /// Trait representing a SVG element
 /// Struct implementing this trait must not reimplement fmt::Display trait
 /// https://developer.mozilla.org/en-US/docs/Web/SVG/Element
-pub trait Element: DynClone {
+pub trait Element: DynClone + AsAny {
     /// This method should return a mutable reference to the attribute field stored in the struct
     fn get_mut_attributes(&mut self) -> &mut Attributes;
     /// This method should return a reference to the attribute field stored in the struct
@@ -30,11 +60,61 @@ pub trait Element: DynClone {
     fn tag_content(&self) -> Option<String> {
         None
     }
-    fn set_id(mut self, value: &str) -> Self
+    /// Container elements should return the list of their child elements.
+    /// Children are rendered after the tag content.
+    /// Implemented to return None by default.
+    fn get_children(&self) -> Option<&Vec<Box<dyn Element>>> {
+        None
+    }
+    /// Mutable counterpart of `get_children`
+    fn get_mut_children(&mut self) -> Option<&mut Vec<Box<dyn Element>>> {
+        None
+    }
+    /// Bounds of the geometry drawn by the element itself, in its own coordinate system and
+    /// without stroke. Implemented by shapes, returns None by default.
+    fn local_bounding_box(&self) -> Option<BoundingBox> {
+        None
+    }
+    /// Bounds of the geometry drawn by the element itself once transformed by `matrix`, without
+    /// stroke. Defaults to the box of the transformed corners of `local_bounding_box`, which is
+    /// only tight for rectangular geometry, shapes with curves compute it from their outline.
+    fn transformed_bounding_box(&self, matrix: &Matrix) -> Option<BoundingBox> {
+        self.local_bounding_box().map(|x| x.transform(matrix))
+    }
+    /// Bounding box of the element and its children in the coordinate system of its parent,
+    /// None when the element draws nothing measurable such as shapes sized in percentages.
+    /// The transforms of the element and its descendants are applied to their geometry, so the
+    /// box stays tight around rotated or skewed curves.
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        let matrix = element_matrix(Matrix::identity(), self.get_attributes());
+        bounds(
+            self.get_attributes(),
+            self.transformed_bounding_box(&matrix),
+            self.as_any().is::<Svg>(),
+            self.get_children(),
+            &matrix,
+            None,
+        )
+    }
+    /// Bounding box including half the stroke width around the stroked shapes.
+    /// Only the `stroke` and `stroke-width` attributes and inline styles are taken into account,
+    /// and miter joins or square caps can extend beyond the box.
+    fn stroke_bounding_box(&self) -> Option<BoundingBox> {
+        let matrix = element_matrix(Matrix::identity(), self.get_attributes());
+        bounds(
+            self.get_attributes(),
+            self.transformed_bounding_box(&matrix),
+            self.as_any().is::<Svg>(),
+            self.get_children(),
+            &matrix,
+            Some(InheritedStroke::default()),
+        )
+    }
+    fn set_id(mut self, value: ElementId) -> Self
     where
         Self: Sized,
     {
-        self.get_mut_attributes().id = Some(value.to_string());
+        self.get_mut_attributes().id = Some(value);
         self
     }
     fn set_stroke(mut self, value: Paint) -> Self
@@ -72,28 +152,274 @@ pub trait Element: DynClone {
         self.get_mut_attributes().class = Some(value);
         self
     }
+    /// Set the transformations applied to the element, in order from right to left
+    fn set_transform(mut self, value: Vec<Transform>) -> Self
+    where
+        Self: Sized,
+    {
+        self.get_mut_attributes().transform = Some(value);
+        self
+    }
+    /// Set the inline `style` attribute
+    fn set_style(mut self, value: StyleDeclarations) -> Self
+    where
+        Self: Sized,
+    {
+        self.get_mut_attributes().style = Some(value);
+        self
+    }
+    /// Set an attribute which has no dedicated setter, such as `data-*` or vendor attributes.
+    /// Setting the same attribute twice replaces its value. Fails for attributes which have a
+    /// dedicated setter, see `Attributes::set_custom`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svg_composer::element::attributes::AttributeName;
+    /// use svg_composer::element::circle::Circle;
+    /// use svg_composer::element::Element;
+    ///
+    /// let circle = Circle::new().set_attribute(AttributeName::data("id").unwrap(), "42").unwrap();
+    /// let fill = AttributeName::from_string("fill".to_string()).unwrap();
+    /// assert!(Circle::new().set_attribute(fill, "red").is_err());
+    /// ```
+    fn set_attribute(mut self, name: AttributeName, value: &str) -> Result<Self, String>
+    where
+        Self: Sized,
+    {
+        self.get_mut_attributes().set_custom(name, value)?;
+        Ok(self)
+    }
+    /// Set a `<title>` child, displayed as a tooltip by most viewers
+    fn set_title(mut self, value: &str) -> Self
+    where
+        Self: Sized,
+    {
+        self.get_mut_attributes().title = Some(value.to_string());
+        self
+    }
+    /// Set a `<desc>` child, a longer text description used by assistive technologies
+    fn set_description(mut self, value: &str) -> Self
+    where
+        Self: Sized,
+    {
+        self.get_mut_attributes().desc = Some(value.to_string());
+        self
+    }
+    fn set_role(mut self, value: &str) -> Self
+    where
+        Self: Sized,
+    {
+        self.get_mut_attributes().role = Some(value.to_string());
+        self
+    }
+    fn set_aria_label(mut self, value: &str) -> Self
+    where
+        Self: Sized,
+    {
+        self.get_mut_attributes().aria_label = Some(value.to_string());
+        self
+    }
+    /// Set the ids of the elements describing this element, separated by spaces
+    fn set_aria_describedby(mut self, value: &str) -> Self
+    where
+        Self: Sized,
+    {
+        self.get_mut_attributes().aria_describedby = Some(value.to_string());
+        self
+    }
 }
 
 dyn_clone::clone_trait_object!(Element);
 
-impl fmt::Display for dyn Element {
+impl<'a> dyn Element + 'a {
+    /// Returns true if the element is of type `T`
+    pub fn is<T: Element + 'static>(&self) -> bool {
+        self.as_any().is::<T>()
+    }
+    /// Returns a reference to the concrete element if it is of type `T`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svg_composer::element::circle::Circle;
+    /// use svg_composer::element::Element;
+    ///
+    /// let element: Box<dyn Element> = Box::new(Circle::new().set_radius(10.));
+    /// let circle = element.downcast_ref::<Circle>().unwrap();
+    /// assert_eq!(circle.radius().unwrap().as_length(), Some(10.));
+    /// ```
+    pub fn downcast_ref<T: Element + 'static>(&self) -> Option<&T> {
+        self.as_any().downcast_ref::<T>()
+    }
+    /// Returns a mutable reference to the concrete element if it is of type `T`
+    pub fn downcast_mut<T: Element + 'static>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut::<T>()
+    }
+}
+
+impl<'a> fmt::Display for dyn Element + 'a {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let mut formatted_attributes = self.get_attributes().to_string();
-        if let Some(tag_content) = self.tag_content() {
-            write!(
-                f,
-                "<{tag_name} {attributes}>\n{content}\n</{tag_name}>",
-                tag_name = self.tag_name(),
-                attributes = formatted_attributes,
-                content = tag_content,
-            )
+        let attributes = self.get_attributes();
+        // The space after the tag name is kept without attributes, like `<circle />`
+        let opening_tag = format!("{} {}", self.tag_name(), attributes);
+        let mut content: Vec<String> = vec![
+            attributes
+                .title
+                .as_ref()
+                .map(|x| format!("<title>{}</title>", escape_xml(x))),
+            attributes
+                .desc
+                .as_ref()
+                .map(|x| format!("<desc>{}</desc>", escape_xml(x))),
+            self.tag_content(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if let Some(children) = self.get_children() {
+            content.extend(children.iter().map(ToString::to_string));
+        }
+
+        if content.is_empty() {
+            write!(f, "<{opening_tag}/>", opening_tag = opening_tag)
         } else {
             write!(
                 f,
-                "<{tag_name} {attributes}/>",
+                "<{opening_tag}>\n{content}\n</{tag_name}>",
+                opening_tag = opening_tag,
+                content = content.join("\n"),
                 tag_name = self.tag_name(),
-                attributes = formatted_attributes,
             )
         }
     }
 }
+
+/// Returns the value of a size in user units, or `default` if it is not set.
+/// Returns None for percentages, which depend on the size of the viewport.
+pub(crate) fn length_or(size: Option<Size>, default: f64) -> Option<f64> {
+    match size {
+        Some(size) => size.as_length(),
+        None => Some(default),
+    }
+}
+
+/// Stroke of an element, inherited from its ancestors when not set
+#[derive(Copy, Clone)]
+struct InheritedStroke {
+    painted: bool,
+    width: f64,
+}
+
+impl Default for InheritedStroke {
+    /// Strokes are not painted and are 1 user unit wide by default
+    fn default() -> Self {
+        InheritedStroke {
+            painted: false,
+            width: 1.,
+        }
+    }
+}
+
+impl InheritedStroke {
+    fn inherit(self, attributes: &Attributes) -> Self {
+        let style = attributes.style.as_ref();
+        let stroke = style
+            .and_then(|x| x.stroke())
+            .or(attributes.stroke.as_ref());
+        let width = style
+            .and_then(|x| x.stroke_width())
+            .or(attributes.stroke_width)
+            .and_then(|x| x.as_length());
+        InheritedStroke {
+            painted: stroke.map_or(self.painted, |x| x.to_string() != "none"),
+            width: width.unwrap_or(self.width),
+        }
+    }
+}
+
+/// Returns the matrix mapping the coordinates of an element to the ones of the ancestor
+/// `parent_matrix` maps to
+fn element_matrix(parent_matrix: Matrix, attributes: &Attributes) -> Matrix {
+    match attributes.transform.as_ref() {
+        Some(transforms) => parent_matrix * Matrix::from_transforms(transforms),
+        None => parent_matrix,
+    }
+}
+
+/// Bounding box of an element from its parts, see `Element::bounding_box`. The geometry of the
+/// element is already transformed by `matrix`, which is also applied to its descendants.
+/// Nested svg elements clip their content to their viewport. Strokes are widened by the
+/// largest scale of the matrix, which overestimates them under non-uniform scales.
+fn bounds(
+    attributes: &Attributes,
+    geometry: Option<BoundingBox>,
+    is_viewport: bool,
+    children: Option<&Vec<Box<dyn Element>>>,
+    matrix: &Matrix,
+    stroke: Option<InheritedStroke>,
+) -> Option<BoundingBox> {
+    let stroke = stroke.map(|x| x.inherit(attributes));
+    match geometry {
+        Some(viewport) if is_viewport => Some(viewport),
+        _ => geometry
+            .map(|x| match stroke {
+                Some(stroke) if stroke.painted => x.expand(stroke.width / 2. * matrix.max_scale()),
+                _ => x,
+            })
+            .into_iter()
+            .chain(children.into_iter().flatten().filter_map(|child| {
+                let matrix = element_matrix(*matrix, child.get_attributes());
+                bounds(
+                    child.get_attributes(),
+                    child.transformed_bounding_box(&matrix),
+                    child.is::<Svg>(),
+                    child.get_children(),
+                    &matrix,
+                    stroke,
+                )
+            }))
+            .reduce(|a, b| a.union(&b)),
+    }
+}
+
+/// Bounds of a shape drawn by `commands` once transformed by `matrix`. Shapes drawing nothing,
+/// like circles without radius, are measured from their local bounds which are then a point or
+/// a segment.
+pub(crate) fn shape_bounding_box(
+    commands: Option<Commands>,
+    local_bounding_box: Option<BoundingBox>,
+    matrix: &Matrix,
+) -> Option<BoundingBox> {
+    match commands {
+        Some(commands) if !commands.is_empty() => commands.transform(matrix).bounding_box(),
+        _ => local_bounding_box.map(|x| x.transform(matrix)),
+    }
+}
+
+/// Iterator over a list of elements and all of their descendants, in depth-first order:
+/// each element is followed by its children before its next sibling
+pub struct DepthFirstIter<'a> {
+    stack: Vec<&'a dyn Element>,
+}
+
+impl<'a> DepthFirstIter<'a> {
+    pub fn new(elements: Vec<&'a dyn Element>) -> Self {
+        let mut stack = elements;
+        stack.reverse();
+        DepthFirstIter { stack }
+    }
+}
+
+impl<'a> Iterator for DepthFirstIter<'a> {
+    type Item = &'a dyn Element;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let element = self.stack.pop()?;
+        if let Some(children) = element.get_children() {
+            self.stack
+                .extend(children.iter().rev().map(|child| child.as_ref()));
+        }
+        Some(element)
+    }
+}
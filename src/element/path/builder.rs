@@ -0,0 +1,264 @@
+use crate::element::path::command::{
+    Arc, Commands, CoordinateType, CubicBezierCurve, End, LineTo, LineToOption, MoveTo,
+    PathSegment, QuadraticBezierCurve,
+};
+use crate::element::Path;
+
+/// Fluent construction of path commands. Methods named after the SVG commands use absolute
+/// coordinates, their `_rel` variants use coordinates relative to the current point.
+///
+/// # Examples
+///
+/// ```
+/// use svg_composer::element::path::builder::PathBuilder;
+///
+/// let commands = PathBuilder::new()
+///     .move_to((11., 1.))
+///     .line_to_rel((10., 20.))
+///     .h_rel(-20.)
+///     .close()
+///     .build();
+/// assert_eq!(commands.to_string(), "M11 1 l10 20 h-20 Z");
+/// ```
+#[derive(Clone, Default)]
+pub struct PathBuilder {
+    commands: Vec<PathSegment>,
+}
+
+impl PathBuilder {
+    pub fn new() -> Self {
+        PathBuilder::default()
+    }
+
+    fn push<S: Into<PathSegment>>(mut self, segment: S) -> Self {
+        self.commands.push(segment.into());
+        self
+    }
+
+    fn move_to_with(self, point: (f64, f64), coordinate_type: CoordinateType) -> Self {
+        self.push(MoveTo {
+            point,
+            coordinate_type,
+        })
+    }
+    pub fn move_to(self, point: (f64, f64)) -> Self {
+        self.move_to_with(point, CoordinateType::Absolute)
+    }
+    pub fn move_to_rel(self, point: (f64, f64)) -> Self {
+        self.move_to_with(point, CoordinateType::Relative)
+    }
+
+    fn line_to_with(
+        self,
+        point: (f64, f64),
+        option: LineToOption,
+        coordinate_type: CoordinateType,
+    ) -> Self {
+        self.push(LineTo {
+            point,
+            option,
+            coordinate_type,
+        })
+    }
+    pub fn line_to(self, point: (f64, f64)) -> Self {
+        self.line_to_with(point, LineToOption::Default, CoordinateType::Absolute)
+    }
+    pub fn line_to_rel(self, point: (f64, f64)) -> Self {
+        self.line_to_with(point, LineToOption::Default, CoordinateType::Relative)
+    }
+    /// Horizontal line to the given x coordinate
+    pub fn h(self, x: f64) -> Self {
+        self.line_to_with((x, 0.), LineToOption::Horizontal, CoordinateType::Absolute)
+    }
+    pub fn h_rel(self, dx: f64) -> Self {
+        self.line_to_with((dx, 0.), LineToOption::Horizontal, CoordinateType::Relative)
+    }
+    /// Vertical line to the given y coordinate
+    pub fn v(self, y: f64) -> Self {
+        self.line_to_with((0., y), LineToOption::Vertical, CoordinateType::Absolute)
+    }
+    pub fn v_rel(self, dy: f64) -> Self {
+        self.line_to_with((0., dy), LineToOption::Vertical, CoordinateType::Relative)
+    }
+
+    fn cubic_to_with(
+        self,
+        control_point_1: Option<(f64, f64)>,
+        control_point_2: (f64, f64),
+        point: (f64, f64),
+        coordinate_type: CoordinateType,
+    ) -> Self {
+        self.push(CubicBezierCurve {
+            point,
+            control_point_1,
+            control_point_2,
+            coordinate_type,
+        })
+    }
+    pub fn cubic_to(
+        self,
+        control_point_1: (f64, f64),
+        control_point_2: (f64, f64),
+        point: (f64, f64),
+    ) -> Self {
+        self.cubic_to_with(
+            Some(control_point_1),
+            control_point_2,
+            point,
+            CoordinateType::Absolute,
+        )
+    }
+    pub fn cubic_to_rel(
+        self,
+        control_point_1: (f64, f64),
+        control_point_2: (f64, f64),
+        point: (f64, f64),
+    ) -> Self {
+        self.cubic_to_with(
+            Some(control_point_1),
+            control_point_2,
+            point,
+            CoordinateType::Relative,
+        )
+    }
+    /// Cubic curve whose first control point is the reflection of the second control point of
+    /// the previous curve
+    pub fn smooth_cubic_to(self, control_point_2: (f64, f64), point: (f64, f64)) -> Self {
+        self.cubic_to_with(None, control_point_2, point, CoordinateType::Absolute)
+    }
+    pub fn smooth_cubic_to_rel(self, control_point_2: (f64, f64), point: (f64, f64)) -> Self {
+        self.cubic_to_with(None, control_point_2, point, CoordinateType::Relative)
+    }
+
+    fn quad_to_with(
+        self,
+        control_point_1: Option<(f64, f64)>,
+        point: (f64, f64),
+        coordinate_type: CoordinateType,
+    ) -> Self {
+        self.push(QuadraticBezierCurve {
+            point,
+            control_point_1,
+            coordinate_type,
+        })
+    }
+    pub fn quad_to(self, control_point: (f64, f64), point: (f64, f64)) -> Self {
+        self.quad_to_with(Some(control_point), point, CoordinateType::Absolute)
+    }
+    pub fn quad_to_rel(self, control_point: (f64, f64), point: (f64, f64)) -> Self {
+        self.quad_to_with(Some(control_point), point, CoordinateType::Relative)
+    }
+    /// Quadratic curve whose control point is the reflection of the control point of the
+    /// previous curve
+    pub fn smooth_quad_to(self, point: (f64, f64)) -> Self {
+        self.quad_to_with(None, point, CoordinateType::Absolute)
+    }
+    pub fn smooth_quad_to_rel(self, point: (f64, f64)) -> Self {
+        self.quad_to_with(None, point, CoordinateType::Relative)
+    }
+
+    fn arc_to_with(
+        self,
+        radius: (f64, f64),
+        x_axis_rotation: f64,
+        large_arc_flag: bool,
+        sweep_flag: bool,
+        point: (f64, f64),
+        coordinate_type: CoordinateType,
+    ) -> Self {
+        self.push(Arc {
+            radius,
+            x_axis_rotation,
+            large_arc_flag,
+            sweep_flag,
+            point,
+            coordinate_type,
+        })
+    }
+    /// Elliptical arc, the rotation of the ellipse is in degrees
+    pub fn arc_to(
+        self,
+        radius: (f64, f64),
+        x_axis_rotation: f64,
+        large_arc_flag: bool,
+        sweep_flag: bool,
+        point: (f64, f64),
+    ) -> Self {
+        self.arc_to_with(
+            radius,
+            x_axis_rotation,
+            large_arc_flag,
+            sweep_flag,
+            point,
+            CoordinateType::Absolute,
+        )
+    }
+    pub fn arc_to_rel(
+        self,
+        radius: (f64, f64),
+        x_axis_rotation: f64,
+        large_arc_flag: bool,
+        sweep_flag: bool,
+        point: (f64, f64),
+    ) -> Self {
+        self.arc_to_with(
+            radius,
+            x_axis_rotation,
+            large_arc_flag,
+            sweep_flag,
+            point,
+            CoordinateType::Relative,
+        )
+    }
+
+    /// Closes the current subpath with a straight line to its start
+    pub fn close(self) -> Self {
+        self.push(End {})
+    }
+
+    pub fn build(self) -> Commands {
+        Commands {
+            commands: self.commands,
+        }
+    }
+    /// Returns a path element drawing the commands
+    pub fn into_path(self) -> Path {
+        Path::new().add_commands(self.commands)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_build_all_commands() {
+        let commands = PathBuilder::new()
+            .move_to((1., 2.))
+            .line_to((3., 4.))
+            .h(5.)
+            .v(6.)
+            .cubic_to((1., 1.), (2., 2.), (3., 3.))
+            .smooth_cubic_to((4., 4.), (5., 5.))
+            .quad_to((6., 6.), (7., 7.))
+            .smooth_quad_to((8., 8.))
+            .arc_to((5., 3.), 30., true, false, (9., 9.))
+            .close()
+            .move_to_rel((1., 1.))
+            .line_to_rel((1., 0.))
+            .h_rel(2.)
+            .v_rel(-2.)
+            .cubic_to_rel((1., 1.), (2., 2.), (3., 3.))
+            .smooth_cubic_to_rel((4., 4.), (5., 5.))
+            .quad_to_rel((6., 6.), (7., 7.))
+            .smooth_quad_to_rel((8., 8.))
+            .arc_to_rel((5., 5.), 0., false, true, (9., 9.))
+            .close()
+            .build();
+        assert_eq!(
+            commands.to_string(),
+            "M1 2 L3 4 H5 V6 C1 1 2 2 3 3 S4 4 5 5 Q6 6 7 7 T8 8 A5 3 30 1 0 9 9 Z \
+             m1 1 l1 0 h2 v-2 c1 1 2 2 3 3 s4 4 5 5 q6 6 7 7 t8 8 a5 5 0 0 1 9 9 Z"
+        );
+    }
+}
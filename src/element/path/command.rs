@@ -1,18 +1,346 @@
-use dyn_clone::DynClone;
 use std::fmt;
+use std::iter::FromIterator;
+use std::slice;
+use std::vec;
 
-pub trait Command: fmt::Display + DynClone {}
-dyn_clone::clone_trait_object!(Command);
-impl Command for MoveTo {}
-impl Command for LineTo {}
-impl Command for CubicBezierCurve {}
-impl Command for QuadraticBezierCurve {}
-impl Command for Arc {}
-impl Command for End {}
+/// A single segment of a path, holding one of the command structs
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PathSegment {
+    MoveTo(MoveTo),
+    LineTo(LineTo),
+    CubicBezierCurve(CubicBezierCurve),
+    QuadraticBezierCurve(QuadraticBezierCurve),
+    Arc(Arc),
+    End(End),
+}
+
+impl PathSegment {
+    /// The point the segment ends at, `None` for `End` which returns to the start of the subpath
+    pub fn point(&self) -> Option<(f64, f64)> {
+        match self {
+            PathSegment::MoveTo(x) => Some(x.point),
+            PathSegment::LineTo(x) => Some(x.point),
+            PathSegment::CubicBezierCurve(x) => Some(x.point),
+            PathSegment::QuadraticBezierCurve(x) => Some(x.point),
+            PathSegment::Arc(x) => Some(x.point),
+            PathSegment::End(_) => None,
+        }
+    }
+    pub fn coordinate_type(&self) -> Option<CoordinateType> {
+        match self {
+            PathSegment::MoveTo(x) => Some(x.coordinate_type),
+            PathSegment::LineTo(x) => Some(x.coordinate_type),
+            PathSegment::CubicBezierCurve(x) => Some(x.coordinate_type),
+            PathSegment::QuadraticBezierCurve(x) => Some(x.coordinate_type),
+            PathSegment::Arc(x) => Some(x.coordinate_type),
+            PathSegment::End(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathSegment::MoveTo(x) => x.fmt(f),
+            PathSegment::LineTo(x) => x.fmt(f),
+            PathSegment::CubicBezierCurve(x) => x.fmt(f),
+            PathSegment::QuadraticBezierCurve(x) => x.fmt(f),
+            PathSegment::Arc(x) => x.fmt(f),
+            PathSegment::End(x) => x.fmt(f),
+        }
+    }
+}
+
+impl From<MoveTo> for PathSegment {
+    fn from(value: MoveTo) -> Self {
+        PathSegment::MoveTo(value)
+    }
+}
+impl From<LineTo> for PathSegment {
+    fn from(value: LineTo) -> Self {
+        PathSegment::LineTo(value)
+    }
+}
+impl From<CubicBezierCurve> for PathSegment {
+    fn from(value: CubicBezierCurve) -> Self {
+        PathSegment::CubicBezierCurve(value)
+    }
+}
+impl From<QuadraticBezierCurve> for PathSegment {
+    fn from(value: QuadraticBezierCurve) -> Self {
+        PathSegment::QuadraticBezierCurve(value)
+    }
+}
+impl From<Arc> for PathSegment {
+    fn from(value: Arc) -> Self {
+        PathSegment::Arc(value)
+    }
+}
+impl From<End> for PathSegment {
+    fn from(value: End) -> Self {
+        PathSegment::End(value)
+    }
+}
 
-#[derive(Clone)]
+/// The segments of a path, rendered as the `d` attribute
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct Commands {
-    pub commands: Vec<Box<dyn Command>>,
+    pub commands: Vec<PathSegment>,
+}
+
+impl Commands {
+    pub fn new() -> Self {
+        Commands::default()
+    }
+    pub fn push<S: Into<PathSegment>>(&mut self, segment: S) {
+        self.commands.push(segment.into());
+    }
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+    pub fn iter(&self) -> slice::Iter<'_, PathSegment> {
+        self.commands.iter()
+    }
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, PathSegment> {
+        self.commands.iter_mut()
+    }
+}
+
+impl Commands {
+    /// Returns the same path with all segments in absolute coordinates.
+    /// Smooth curves and horizontal and vertical lines are kept, the coordinate of horizontal
+    /// and vertical lines which is not rendered is set to the one of the current point.
+    pub fn to_absolute(&self) -> Commands {
+        let mut pen = Pen::default();
+        self.iter().map(|x| pen.step(x, false)).collect()
+    }
+
+    /// Returns the same path with all segments in coordinates relative to the end of the
+    /// previous segment, the first move being relative to the origin.
+    /// Smooth curves and horizontal and vertical lines are kept.
+    pub fn to_relative(&self) -> Commands {
+        let mut pen = Pen::default();
+        self.iter()
+            .map(|segment| {
+                let start = pen.current_point;
+                let relative = |point: (f64, f64)| (point.0 - start.0, point.1 - start.1);
+                match pen.step(segment, false) {
+                    PathSegment::MoveTo(x) => PathSegment::MoveTo(MoveTo {
+                        point: relative(x.point),
+                        coordinate_type: CoordinateType::Relative,
+                    }),
+                    PathSegment::LineTo(x) => {
+                        let (dx, dy) = relative(x.point);
+                        PathSegment::LineTo(LineTo {
+                            point: match x.option {
+                                LineToOption::Default => (dx, dy),
+                                LineToOption::Horizontal => (dx, 0.),
+                                LineToOption::Vertical => (0., dy),
+                            },
+                            option: x.option,
+                            coordinate_type: CoordinateType::Relative,
+                        })
+                    }
+                    PathSegment::CubicBezierCurve(x) => {
+                        PathSegment::CubicBezierCurve(CubicBezierCurve {
+                            point: relative(x.point),
+                            control_point_1: x.control_point_1.map(relative),
+                            control_point_2: relative(x.control_point_2),
+                            coordinate_type: CoordinateType::Relative,
+                        })
+                    }
+                    PathSegment::QuadraticBezierCurve(x) => {
+                        PathSegment::QuadraticBezierCurve(QuadraticBezierCurve {
+                            point: relative(x.point),
+                            control_point_1: x.control_point_1.map(relative),
+                            coordinate_type: CoordinateType::Relative,
+                        })
+                    }
+                    PathSegment::Arc(x) => PathSegment::Arc(Arc {
+                        point: relative(x.point),
+                        coordinate_type: CoordinateType::Relative,
+                        ..x
+                    }),
+                    PathSegment::End(x) => PathSegment::End(x),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the same path using only absolute moves, lines, cubic and quadratic curves with
+    /// explicit control points, arcs and ends. Horizontal and vertical lines become lines and
+    /// smooth curves get the reflected control point of the previous curve.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svg_composer::element::path::builder::PathBuilder;
+    ///
+    /// let commands = PathBuilder::new()
+    ///     .move_to((10., 10.))
+    ///     .h_rel(10.)
+    ///     .cubic_to_rel((0., 5.), (5., 10.), (10., 10.))
+    ///     .smooth_cubic_to_rel((10., 0.), (10., -10.))
+    ///     .build();
+    /// assert_eq!(
+    ///     commands.normalize().to_string(),
+    ///     "M10 10 L20 10 C20 15 25 20 30 20 C35 20 40 20 40 10"
+    /// );
+    /// ```
+    pub fn normalize(&self) -> Commands {
+        let mut pen = Pen::default();
+        self.iter().map(|x| pen.step(x, true)).collect()
+    }
+}
+
+/// Position of the pen while walking through the segments of a path
+#[derive(Copy, Clone, Debug, Default)]
+struct Pen {
+    current_point: (f64, f64),
+    subpath_start: (f64, f64),
+    /// Second control point of the previous segment, when it is a cubic curve
+    last_cubic_control: Option<(f64, f64)>,
+    /// Control point of the previous segment, when it is a quadratic curve
+    last_quadratic_control: Option<(f64, f64)>,
+}
+
+impl Pen {
+    fn absolute(&self, point: (f64, f64), coordinate_type: CoordinateType) -> (f64, f64) {
+        match coordinate_type {
+            CoordinateType::Absolute => point,
+            CoordinateType::Relative => (
+                self.current_point.0 + point.0,
+                self.current_point.1 + point.1,
+            ),
+        }
+    }
+
+    /// Reflection of a control point of the previous curve around the current point, or the
+    /// current point if the previous segment is not a curve of the same kind
+    fn reflect(&self, control_point: Option<(f64, f64)>) -> (f64, f64) {
+        match control_point {
+            Some(control_point) => (
+                2. * self.current_point.0 - control_point.0,
+                2. * self.current_point.1 - control_point.1,
+            ),
+            None => self.current_point,
+        }
+    }
+
+    /// Returns the segment in absolute coordinates and moves the pen to its end. When `expand`
+    /// is set, horizontal and vertical lines and smooth curves are replaced by their general form.
+    fn step(&mut self, segment: &PathSegment, expand: bool) -> PathSegment {
+        let mut last_cubic_control = None;
+        let mut last_quadratic_control = None;
+        let absolute = match *segment {
+            PathSegment::MoveTo(x) => {
+                let point = self.absolute(x.point, x.coordinate_type);
+                self.subpath_start = point;
+                PathSegment::MoveTo(MoveTo {
+                    point,
+                    coordinate_type: CoordinateType::Absolute,
+                })
+            }
+            PathSegment::LineTo(x) => {
+                let point = match (x.option, x.coordinate_type) {
+                    (LineToOption::Default, _) => self.absolute(x.point, x.coordinate_type),
+                    (LineToOption::Horizontal, CoordinateType::Absolute) => {
+                        (x.point.0, self.current_point.1)
+                    }
+                    (LineToOption::Horizontal, CoordinateType::Relative) => {
+                        (self.current_point.0 + x.point.0, self.current_point.1)
+                    }
+                    (LineToOption::Vertical, CoordinateType::Absolute) => {
+                        (self.current_point.0, x.point.1)
+                    }
+                    (LineToOption::Vertical, CoordinateType::Relative) => {
+                        (self.current_point.0, self.current_point.1 + x.point.1)
+                    }
+                };
+                PathSegment::LineTo(LineTo {
+                    point,
+                    option: if expand {
+                        LineToOption::Default
+                    } else {
+                        x.option
+                    },
+                    coordinate_type: CoordinateType::Absolute,
+                })
+            }
+            PathSegment::CubicBezierCurve(x) => {
+                let control_point_2 = self.absolute(x.control_point_2, x.coordinate_type);
+                let control_point_1 = match x.control_point_1 {
+                    Some(control_point_1) => {
+                        Some(self.absolute(control_point_1, x.coordinate_type))
+                    }
+                    None if expand => Some(self.reflect(self.last_cubic_control)),
+                    None => None,
+                };
+                last_cubic_control = Some(control_point_2);
+                PathSegment::CubicBezierCurve(CubicBezierCurve {
+                    point: self.absolute(x.point, x.coordinate_type),
+                    control_point_1,
+                    control_point_2,
+                    coordinate_type: CoordinateType::Absolute,
+                })
+            }
+            PathSegment::QuadraticBezierCurve(x) => {
+                let control_point = match x.control_point_1 {
+                    Some(control_point_1) => self.absolute(control_point_1, x.coordinate_type),
+                    None => self.reflect(self.last_quadratic_control),
+                };
+                last_quadratic_control = Some(control_point);
+                PathSegment::QuadraticBezierCurve(QuadraticBezierCurve {
+                    point: self.absolute(x.point, x.coordinate_type),
+                    control_point_1: if x.control_point_1.is_some() || expand {
+                        Some(control_point)
+                    } else {
+                        None
+                    },
+                    coordinate_type: CoordinateType::Absolute,
+                })
+            }
+            PathSegment::Arc(x) => PathSegment::Arc(Arc {
+                point: self.absolute(x.point, x.coordinate_type),
+                coordinate_type: CoordinateType::Absolute,
+                ..x
+            }),
+            PathSegment::End(x) => PathSegment::End(x),
+        };
+        self.current_point = absolute.point().unwrap_or(self.subpath_start);
+        self.last_cubic_control = last_cubic_control;
+        self.last_quadratic_control = last_quadratic_control;
+        absolute
+    }
+}
+
+impl FromIterator<PathSegment> for Commands {
+    fn from_iter<T: IntoIterator<Item = PathSegment>>(iter: T) -> Self {
+        Commands {
+            commands: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for Commands {
+    type Item = PathSegment;
+    type IntoIter = vec::IntoIter<PathSegment>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.commands.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Commands {
+    type Item = &'a PathSegment;
+    type IntoIter = slice::Iter<'a, PathSegment>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.commands.iter()
+    }
 }
 
 impl fmt::Display for Commands {
@@ -29,20 +357,20 @@ impl fmt::Display for Commands {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum CoordinateType {
     Absolute,
     Relative,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum LineToOption {
     Default,
     Vertical,
     Horizontal,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct MoveTo {
     pub point: (f64, f64),
     pub coordinate_type: CoordinateType,
@@ -57,7 +385,7 @@ impl fmt::Display for MoveTo {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct LineTo {
     pub point: (f64, f64),
     pub option: LineToOption,
@@ -85,7 +413,7 @@ impl fmt::Display for LineTo {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct CubicBezierCurve {
     pub point: (f64, f64),
     pub control_point_1: Option<(f64, f64)>,
@@ -129,7 +457,7 @@ impl fmt::Display for CubicBezierCurve {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct QuadraticBezierCurve {
     pub point: (f64, f64),
     pub control_point_1: Option<(f64, f64)>,
@@ -158,7 +486,7 @@ impl fmt::Display for QuadraticBezierCurve {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Arc {
     pub radius: (f64, f64),
     pub x_axis_rotation: f64,
@@ -208,7 +536,7 @@ impl fmt::Display for Arc {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct End {}
 
 impl fmt::Display for End {
@@ -220,6 +548,55 @@ impl fmt::Display for End {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::element::path::builder::PathBuilder;
+
+    #[test]
+    fn should_convert_between_absolute_and_relative_coordinates() {
+        let commands = PathBuilder::new()
+            .move_to_rel((10., 10.))
+            .h_rel(10.)
+            .v(30.)
+            .quad_to_rel((5., 5.), (10., 0.))
+            .smooth_quad_to((40., 30.))
+            .arc_to_rel((5., 5.), 0., false, true, (0., 10.))
+            .close()
+            .move_to_rel((1., 1.))
+            .line_to((20., 20.))
+            .build();
+        let absolute = commands.to_absolute();
+        assert_eq!(
+            absolute.to_string(),
+            "M10 10 H20 V30 Q25 35 30 30 T40 30 A5 5 0 0 1 40 40 Z M11 11 L20 20"
+        );
+        assert_eq!(
+            absolute.to_relative().to_string(),
+            "m10 10 h10 v20 q5 5 10 0 t10 0 a5 5 0 0 1 0 10 Z m1 1 l9 9"
+        );
+        assert_eq!(absolute.to_relative().to_absolute(), absolute);
+        assert_eq!(
+            commands.normalize().to_string(),
+            "M10 10 L20 10 L20 30 Q25 35 30 30 Q35 25 40 30 A5 5 0 0 1 40 40 Z M11 11 L20 20"
+        );
+    }
+
+    #[test]
+    fn should_iterate_and_compare_segments() {
+        let mut commands = Commands::new();
+        commands.push(MoveTo {
+            point: (1.0, 2.0),
+            coordinate_type: CoordinateType::Absolute,
+        });
+        commands.push(End {});
+        assert_eq!(commands.len(), 2);
+        assert_eq!(
+            commands.iter().map(PathSegment::point).collect::<Vec<_>>(),
+            vec![Some((1.0, 2.0)), None]
+        );
+        let copy = commands.clone().into_iter().collect::<Commands>();
+        assert_eq!(copy, commands);
+        assert!(matches!(copy.commands[1], PathSegment::End(_)));
+        assert_eq!(copy.to_string(), "M1 2 Z");
+    }
 
     #[test]
     fn should_format_move_to() {
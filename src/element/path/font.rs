@@ -0,0 +1,207 @@
+use crate::element::path::command::{
+    Commands, CoordinateType, CubicBezierCurve, End, LineTo, LineToOption, MoveTo, PathSegment,
+    QuadraticBezierCurve,
+};
+use crate::element::Path;
+use std::fs;
+use ttf_parser::{Face, GlyphId, OutlineBuilder};
+
+/// A font loaded from a TrueType or OpenType file, used to convert text into outlines.
+/// Outlined text does not depend on the fonts installed on the rendering side and can be
+/// drawn by devices which do not support the `<text>` element, such as pen plotters.
+///
+/// # Examples
+///
+/// ```no_run
+/// use svg_composer::element::path::font::Font;
+///
+/// let font = Font::from_file("DejaVuSans.ttf").unwrap();
+/// let path = font.text_to_path("Hello", 12.0, (10.0, 50.0));
+/// ```
+#[derive(Clone)]
+pub struct Font {
+    data: Vec<u8>,
+    index: u32,
+}
+
+impl Font {
+    /// Loads the first face of a font file
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, String> {
+        let data = fs::read(path.as_ref()).map_err(|e| {
+            format!(
+                "Could not read font file {}: {}",
+                path.as_ref().display(),
+                e
+            )
+        })?;
+        Self::from_bytes(data)
+    }
+
+    /// Loads the first face of a font from its raw content
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, String> {
+        Self::from_bytes_with_index(data, 0)
+    }
+
+    /// Loads a specific face of a font collection (.ttc) from its raw content
+    pub fn from_bytes_with_index(data: Vec<u8>, index: u32) -> Result<Self, String> {
+        Face::parse(&data, index).map_err(|e| format!("Could not parse font: {}", e))?;
+        Ok(Font { data, index })
+    }
+
+    fn face(&self) -> Face<'_> {
+        // The face was successfully parsed when the font was constructed
+        Face::parse(&self.data, self.index).unwrap()
+    }
+
+    /// Returns the outlines of `text` as path commands
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to outline, new lines start a new line of text
+    /// * `font_size` - The size of the font in user units (the size of the em square)
+    /// * `origin` - The position of the start of the baseline of the first line
+    pub fn text_to_commands(&self, text: &str, font_size: f64, origin: (f64, f64)) -> Commands {
+        let face = self.face();
+        let scale = font_size / face.units_per_em() as f64;
+        let line_height = (face.ascender() as f64 - face.descender() as f64
+            + face.line_gap() as f64)
+            * scale;
+        let mut builder = GlyphOutlineBuilder {
+            commands: Vec::new(),
+            origin,
+            scale,
+        };
+
+        for line in text.lines() {
+            let mut previous_glyph: Option<GlyphId> = None;
+            for c in line.chars() {
+                let glyph = face.glyph_index(c).unwrap_or(GlyphId(0));
+                if let Some(previous_glyph) = previous_glyph {
+                    builder.origin.0 += kerning(&face, previous_glyph, glyph) * scale;
+                }
+                face.outline_glyph(glyph, &mut builder);
+                builder.origin.0 += face.glyph_hor_advance(glyph).unwrap_or(0) as f64 * scale;
+                previous_glyph = Some(glyph);
+            }
+            builder.origin = (origin.0, builder.origin.1 + line_height);
+        }
+
+        Commands {
+            commands: builder.commands,
+        }
+    }
+
+    /// Returns the outlines of `text` as a path element.
+    /// See [`Font::text_to_commands`] for a description of the arguments.
+    pub fn text_to_path(&self, text: &str, font_size: f64, origin: (f64, f64)) -> Path {
+        Path::new().add_commands(self.text_to_commands(text, font_size, origin).commands)
+    }
+}
+
+/// Horizontal kerning between two glyphs in font units, as defined by the `kern` table
+fn kerning(face: &Face, left: GlyphId, right: GlyphId) -> f64 {
+    face.tables()
+        .kern
+        .map(|kern| {
+            kern.subtables
+                .into_iter()
+                .filter(|subtable| {
+                    subtable.horizontal && !subtable.variable && !subtable.has_cross_stream
+                })
+                .filter_map(|subtable| subtable.glyphs_kerning(left, right))
+                .map(f64::from)
+                .sum()
+        })
+        .unwrap_or(0.0)
+}
+
+/// Collects glyph outlines as absolute path commands, converting from font units
+/// (y axis pointing up) to user units (y axis pointing down)
+struct GlyphOutlineBuilder {
+    commands: Vec<PathSegment>,
+    origin: (f64, f64),
+    scale: f64,
+}
+
+impl GlyphOutlineBuilder {
+    fn to_user_space(&self, x: f32, y: f32) -> (f64, f64) {
+        (
+            self.origin.0 + x as f64 * self.scale,
+            self.origin.1 - y as f64 * self.scale,
+        )
+    }
+}
+
+impl OutlineBuilder for GlyphOutlineBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let point = self.to_user_space(x, y);
+        self.commands.push(PathSegment::MoveTo(MoveTo {
+            point,
+            coordinate_type: CoordinateType::Absolute,
+        }));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let point = self.to_user_space(x, y);
+        self.commands.push(PathSegment::LineTo(LineTo {
+            point,
+            option: LineToOption::Default,
+            coordinate_type: CoordinateType::Absolute,
+        }));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let control_point_1 = Some(self.to_user_space(x1, y1));
+        let point = self.to_user_space(x, y);
+        self.commands
+            .push(PathSegment::QuadraticBezierCurve(QuadraticBezierCurve {
+                point,
+                control_point_1,
+                coordinate_type: CoordinateType::Absolute,
+            }));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let control_point_1 = Some(self.to_user_space(x1, y1));
+        let control_point_2 = self.to_user_space(x2, y2);
+        let point = self.to_user_space(x, y);
+        self.commands
+            .push(PathSegment::CubicBezierCurve(CubicBezierCurve {
+                point,
+                control_point_1,
+                control_point_2,
+                coordinate_type: CoordinateType::Absolute,
+            }));
+    }
+
+    fn close(&mut self) {
+        self.commands.push(PathSegment::End(End {}));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_reject_invalid_font() {
+        assert!(Font::from_bytes(vec![0, 1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn should_flip_and_scale_glyph_outlines() {
+        let mut builder = GlyphOutlineBuilder {
+            commands: Vec::new(),
+            origin: (10.0, 50.0),
+            scale: 0.5,
+        };
+        builder.move_to(0.0, 0.0);
+        builder.line_to(20.0, 40.0);
+        builder.quad_to(40.0, 40.0, 40.0, 0.0);
+        builder.close();
+        let commands = Commands {
+            commands: builder.commands,
+        };
+        assert_eq!(commands.to_string(), "M10 50 L20 30 Q30 30 30 50 Z");
+    }
+}
@@ -0,0 +1,981 @@
+use crate::element::path::command::{
+    Arc, Commands, CoordinateType, CubicBezierCurve, LineTo, LineToOption, PathSegment,
+};
+use crate::geometry::{BoundingBox, Matrix};
+use std::f64::consts::PI;
+
+/// Number of times curves are always split in half before checking their flatness, so that
+/// curves whose middle point lies on their chord, such as S shapes, are not taken for lines
+const MIN_SUBDIVISIONS: u32 = 3;
+/// Limit on the number of times curves are split in half, for tolerances too small to reach
+const MAX_SUBDIVISIONS: u32 = 16;
+/// Limit on the number of cubic curves approximating an arc
+const MAX_ARC_CUBICS: usize = 1024;
+
+/// A point of a path with the direction of the path at this point
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PathSample {
+    pub point: (f64, f64),
+    /// Unit vector in the direction of the path, zero where the direction is undefined
+    /// such as on a path of zero length
+    pub tangent: (f64, f64),
+    /// The tangent rotated by a quarter turn, clockwise in the y-down coordinate system of SVG
+    pub normal: (f64, f64),
+}
+
+impl PathSample {
+    fn new(point: (f64, f64), direction: (f64, f64)) -> Self {
+        let norm = (direction.0 * direction.0 + direction.1 * direction.1).sqrt();
+        let tangent = if norm > 0. {
+            (direction.0 / norm, direction.1 / norm)
+        } else {
+            (0., 0.)
+        };
+        PathSample {
+            point,
+            tangent,
+            normal: (-tangent.1, tangent.0),
+        }
+    }
+}
+
+/// Elliptical arc in center parameterization, all angles are in radians.
+/// The point of the ellipse at the angle θ is
+/// center + rotation(x_axis_rotation) * (radius.0 * cos(θ), radius.1 * sin(θ)).
+///
+/// # Examples
+///
+/// ```
+/// use svg_composer::element::path::command::{Arc, CoordinateType};
+/// use svg_composer::element::path::geometry::CenterArc;
+///
+/// // Half circle from (0, 0) to (10, 0) passing above the x axis
+/// let arc = Arc::new_circular_ellipse((10., 0.), 5., false, true, CoordinateType::Absolute);
+/// let center_arc = CenterArc::from_arc((0., 0.), &arc).unwrap();
+/// assert_eq!(center_arc.center, (5., 0.));
+/// assert_eq!(center_arc.sweep_angle, std::f64::consts::PI);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CenterArc {
+    pub center: (f64, f64),
+    pub radius: (f64, f64),
+    /// Rotation of the x axis of the ellipse
+    pub x_axis_rotation: f64,
+    /// Angle of the start point on the ellipse, before rotation
+    pub start_angle: f64,
+    /// Angle from the start point to the end point, positive in the direction of
+    /// increasing angles (clockwise in the y-down coordinate system of SVG)
+    pub sweep_angle: f64,
+}
+
+impl CenterArc {
+    /// Converts an arc starting at the current point `start` from the endpoint
+    /// parameterization of SVG, scaling the radii up if they are too small to reach the end
+    /// point as required by the specification.
+    /// Returns `None` for arcs drawn as straight lines, with a zero radius or no length.
+    /// https://www.w3.org/TR/SVG11/implnote.html#ArcConversionEndpointToCenter
+    pub fn from_arc(start: (f64, f64), arc: &Arc) -> Option<Self> {
+        let end = match arc.coordinate_type {
+            CoordinateType::Absolute => arc.point,
+            CoordinateType::Relative => (start.0 + arc.point.0, start.1 + arc.point.1),
+        };
+        let (mut rx, mut ry) = (arc.radius.0.abs(), arc.radius.1.abs());
+        if rx == 0. || ry == 0. || start == end {
+            return None;
+        }
+        let phi = arc.x_axis_rotation.to_radians();
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        let half_dx = (start.0 - end.0) / 2.;
+        let half_dy = (start.1 - end.1) / 2.;
+        let x1 = cos_phi * half_dx + sin_phi * half_dy;
+        let y1 = -sin_phi * half_dx + cos_phi * half_dy;
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+        if lambda > 1. {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+        let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+        let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+        let sign = if arc.large_arc_flag == arc.sweep_flag {
+            -1.
+        } else {
+            1.
+        };
+        let coefficient = sign * (numerator / denominator).max(0.).sqrt();
+        let center_x1 = coefficient * rx * y1 / ry;
+        let center_y1 = -coefficient * ry * x1 / rx;
+        let center = (
+            cos_phi * center_x1 - sin_phi * center_y1 + (start.0 + end.0) / 2.,
+            sin_phi * center_x1 + cos_phi * center_y1 + (start.1 + end.1) / 2.,
+        );
+        let start_vector = ((x1 - center_x1) / rx, (y1 - center_y1) / ry);
+        let end_vector = ((-x1 - center_x1) / rx, (-y1 - center_y1) / ry);
+        let start_angle = angle((1., 0.), start_vector);
+        let mut sweep_angle = angle(start_vector, end_vector) % (2. * PI);
+        if !arc.sweep_flag && sweep_angle > 0. {
+            sweep_angle -= 2. * PI;
+        } else if arc.sweep_flag && sweep_angle < 0. {
+            sweep_angle += 2. * PI;
+        }
+        Some(CenterArc {
+            center,
+            radius: (rx, ry),
+            x_axis_rotation: phi,
+            start_angle,
+            sweep_angle,
+        })
+    }
+
+    /// Returns the point of the ellipse at the given angle
+    pub fn point_at_angle(&self, angle: f64) -> (f64, f64) {
+        let (sin_phi, cos_phi) = self.x_axis_rotation.sin_cos();
+        let (sin, cos) = angle.sin_cos();
+        (
+            self.center.0 + self.radius.0 * cos_phi * cos - self.radius.1 * sin_phi * sin,
+            self.center.1 + self.radius.0 * sin_phi * cos + self.radius.1 * cos_phi * sin,
+        )
+    }
+
+    /// Derivative of `point_at_angle` with respect to the angle
+    fn derivative_at_angle(&self, angle: f64) -> (f64, f64) {
+        let (sin_phi, cos_phi) = self.x_axis_rotation.sin_cos();
+        let (sin, cos) = angle.sin_cos();
+        (
+            -self.radius.0 * cos_phi * sin - self.radius.1 * sin_phi * cos,
+            -self.radius.0 * sin_phi * sin + self.radius.1 * cos_phi * cos,
+        )
+    }
+
+    /// Returns the point of the arc for the parameter `t`, from 0 at its start to 1 at its end
+    pub fn point_at(&self, t: f64) -> (f64, f64) {
+        self.point_at_angle(self.start_angle + t * self.sweep_angle)
+    }
+
+    fn derivative_at(&self, t: f64) -> (f64, f64) {
+        let derivative = self.derivative_at_angle(self.start_angle + t * self.sweep_angle);
+        (
+            self.sweep_angle * derivative.0,
+            self.sweep_angle * derivative.1,
+        )
+    }
+
+    /// Returns the endpoint parameterization of the arc, in absolute coordinates
+    pub fn to_arc(&self) -> Arc {
+        Arc {
+            radius: self.radius,
+            x_axis_rotation: self.x_axis_rotation.to_degrees(),
+            large_arc_flag: self.sweep_angle.abs() > PI,
+            sweep_flag: self.sweep_angle > 0.,
+            point: self.point_at(1.),
+            coordinate_type: CoordinateType::Absolute,
+        }
+    }
+
+    /// Approximates the arc with absolute cubic Bézier curves, each spanning at most a quarter
+    /// of the ellipse, such that they are no further than `tolerance` from the arc
+    pub fn to_cubics(&self, tolerance: f64) -> Vec<CubicBezierCurve> {
+        let radius = self.radius.0.max(self.radius.1);
+        let mut count = (self.sweep_angle.abs() / (PI / 2.)).ceil().max(1.) as usize;
+        // Maximum distance between a circular arc of the given angle and its cubic approximation
+        let error = |angle: f64| {
+            let quarter = angle / 4.;
+            radius * 4. / 27. * quarter.sin().powi(6) / quarter.cos().powi(2)
+        };
+        while count < MAX_ARC_CUBICS && error(self.sweep_angle.abs() / count as f64) > tolerance {
+            count *= 2;
+        }
+        let step = self.sweep_angle / count as f64;
+        // Length of the tangents giving the best approximation of a circular arc
+        let alpha = 4. / 3. * (step / 4.).tan();
+        (0..count)
+            .map(|i| {
+                let start_angle = self.start_angle + i as f64 * step;
+                let end_angle = start_angle + step;
+                let (start, end) = (
+                    self.point_at_angle(start_angle),
+                    self.point_at_angle(end_angle),
+                );
+                let (start_tangent, end_tangent) = (
+                    self.derivative_at_angle(start_angle),
+                    self.derivative_at_angle(end_angle),
+                );
+                CubicBezierCurve {
+                    point: end,
+                    control_point_1: Some((
+                        start.0 + alpha * start_tangent.0,
+                        start.1 + alpha * start_tangent.1,
+                    )),
+                    control_point_2: (end.0 - alpha * end_tangent.0, end.1 - alpha * end_tangent.1),
+                    coordinate_type: CoordinateType::Absolute,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Arc {
+    /// Returns the arc with the linear part of the matrix applied to its ellipse and ending at
+    /// `point`, which is already transformed. The ellipse of the arc is the image of the unit
+    /// circle by linear(matrix) * rotate(x_axis_rotation) * scale(radius), whose axes are
+    /// found from the eigenvalues of M * transpose(M). A reflection reverses the sweep.
+    fn transform(&self, matrix: &Matrix, point: (f64, f64)) -> Arc {
+        let ellipse = Matrix {
+            e: 0.,
+            f: 0.,
+            ..*matrix
+        } * Matrix::rotate(self.x_axis_rotation, 0., 0.)
+            * Matrix::scale(self.radius.0, self.radius.1);
+        let p = ellipse.a * ellipse.a + ellipse.c * ellipse.c;
+        let q = ellipse.b * ellipse.b + ellipse.d * ellipse.d;
+        let r = ellipse.a * ellipse.b + ellipse.c * ellipse.d;
+        let mean = (p + q) / 2.;
+        let deviation = (((p - q) / 2.).powi(2) + r * r).sqrt();
+        let reflected = matrix.determinant() < 0.;
+        Arc {
+            radius: (
+                (mean + deviation).max(0.).sqrt(),
+                (mean - deviation).max(0.).sqrt(),
+            ),
+            x_axis_rotation: (0.5 * (2. * r).atan2(p - q)).to_degrees(),
+            large_arc_flag: self.large_arc_flag,
+            sweep_flag: self.sweep_flag != reflected,
+            point,
+            coordinate_type: self.coordinate_type,
+        }
+    }
+
+    /// Approximates the arc starting at the current point `start` with absolute cubic Bézier
+    /// curves no further than `tolerance` from it, see `CenterArc::to_cubics`.
+    /// Arcs drawn as straight lines are returned as a single straight curve, and arcs of no
+    /// length as no curve.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svg_composer::element::path::command::{Arc, CoordinateType};
+    ///
+    /// let arc = Arc::new_circular_ellipse((10., 10.), 10., false, true, CoordinateType::Relative);
+    /// let cubics = arc.to_cubics((0., 0.), 0.01);
+    /// assert_eq!(cubics.len(), 1);
+    /// assert_eq!(cubics[0].point, (10., 10.));
+    /// ```
+    pub fn to_cubics(&self, start: (f64, f64), tolerance: f64) -> Vec<CubicBezierCurve> {
+        if let Some(center_arc) = CenterArc::from_arc(start, self) {
+            return center_arc.to_cubics(tolerance);
+        }
+        let end = match self.coordinate_type {
+            CoordinateType::Absolute => self.point,
+            CoordinateType::Relative => (start.0 + self.point.0, start.1 + self.point.1),
+        };
+        if start == end {
+            return Vec::new();
+        }
+        vec![CubicBezierCurve {
+            point: end,
+            control_point_1: Some(start),
+            control_point_2: end,
+            coordinate_type: CoordinateType::Absolute,
+        }]
+    }
+}
+
+/// Signed angle in radians from the vector `u` to the vector `v`
+fn angle(u: (f64, f64), v: (f64, f64)) -> f64 {
+    (u.0 * v.1 - u.1 * v.0).atan2(u.0 * v.0 + u.1 * v.1)
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+fn lerp(a: (f64, f64), b: (f64, f64), t: f64) -> (f64, f64) {
+    (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+}
+
+/// A drawn segment of a path in absolute coordinates, parameterized over [0, 1]
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Curve {
+    Line((f64, f64), (f64, f64)),
+    Quadratic((f64, f64), (f64, f64), (f64, f64)),
+    Cubic((f64, f64), (f64, f64), (f64, f64), (f64, f64)),
+    Arc(CenterArc),
+}
+
+impl Curve {
+    fn point_at(&self, t: f64) -> (f64, f64) {
+        match *self {
+            Curve::Line(p0, p1) => lerp(p0, p1, t),
+            Curve::Quadratic(p0, c, p1) => lerp(lerp(p0, c, t), lerp(c, p1, t), t),
+            Curve::Cubic(p0, c1, c2, p1) => {
+                let a = lerp(p0, c1, t);
+                let b = lerp(c1, c2, t);
+                let c = lerp(c2, p1, t);
+                lerp(lerp(a, b, t), lerp(b, c, t), t)
+            }
+            Curve::Arc(arc) => arc.point_at(t),
+        }
+    }
+
+    fn derivative_at(&self, t: f64) -> (f64, f64) {
+        let difference = |a: (f64, f64), b: (f64, f64), factor: f64| {
+            ((b.0 - a.0) * factor, (b.1 - a.1) * factor)
+        };
+        match *self {
+            Curve::Line(p0, p1) => difference(p0, p1, 1.),
+            Curve::Quadratic(p0, c, p1) => difference(lerp(p0, c, t), lerp(c, p1, t), 2.),
+            Curve::Cubic(p0, c1, c2, p1) => {
+                let a = lerp(p0, c1, t);
+                let b = lerp(c1, c2, t);
+                let c = lerp(c2, p1, t);
+                difference(lerp(a, b, t), lerp(b, c, t), 3.)
+            }
+            Curve::Arc(arc) => arc.derivative_at(t),
+        }
+    }
+
+    /// Direction of the curve at `t`, using a neighbouring parameter where the derivative
+    /// vanishes such as at a control point coinciding with an end point
+    fn direction_at(&self, t: f64) -> (f64, f64) {
+        let derivative = self.derivative_at(t);
+        if derivative != (0., 0.) {
+            return derivative;
+        }
+        let (before, after) = ((t - 1e-6).max(0.), (t + 1e-6).min(1.));
+        let (a, b) = (self.point_at(before), self.point_at(after));
+        (b.0 - a.0, b.1 - a.1)
+    }
+
+    /// Returns the parameters in (0, 1) where the x or y coordinate of the curve is extremal
+    fn extrema(&self) -> Vec<f64> {
+        let mut parameters = Vec::<f64>::new();
+        match *self {
+            Curve::Line(..) => {}
+            Curve::Quadratic(p0, c, p1) => {
+                for (a, b, c) in [(p0.0, c.0, p1.0), (p0.1, c.1, p1.1)].iter() {
+                    let denominator = a - 2. * b + c;
+                    if denominator != 0. {
+                        parameters.push((a - b) / denominator);
+                    }
+                }
+            }
+            Curve::Cubic(p0, c1, c2, p1) => {
+                let axes = [(p0.0, c1.0, c2.0, p1.0), (p0.1, c1.1, c2.1, p1.1)];
+                for (p0, p1, p2, p3) in axes.iter() {
+                    // Roots of the derivative a t^2 + b t + c, up to a constant factor
+                    let a = -p0 + 3. * p1 - 3. * p2 + p3;
+                    let b = 2. * (p0 - 2. * p1 + p2);
+                    let c = p1 - p0;
+                    if a.abs() < 1e-12 {
+                        if b != 0. {
+                            parameters.push(-c / b);
+                        }
+                    } else {
+                        let discriminant = b * b - 4. * a * c;
+                        if discriminant >= 0. {
+                            let root = discriminant.sqrt();
+                            parameters.push((-b + root) / (2. * a));
+                            parameters.push((-b - root) / (2. * a));
+                        }
+                    }
+                }
+            }
+            Curve::Arc(arc) => {
+                let (sin_phi, cos_phi) = arc.x_axis_rotation.sin_cos();
+                let x_angle = (-arc.radius.1 * sin_phi).atan2(arc.radius.0 * cos_phi);
+                let y_angle = (arc.radius.1 * cos_phi).atan2(arc.radius.0 * sin_phi);
+                for angle in [x_angle, x_angle + PI, y_angle, y_angle + PI].iter() {
+                    let delta = if arc.sweep_angle >= 0. {
+                        (angle - arc.start_angle).rem_euclid(2. * PI)
+                    } else {
+                        (arc.start_angle - angle).rem_euclid(2. * PI)
+                    };
+                    if delta <= arc.sweep_angle.abs() {
+                        parameters.push(delta / arc.sweep_angle.abs());
+                    }
+                }
+            }
+        }
+        parameters.retain(|t| *t > 0. && *t < 1.);
+        parameters
+    }
+
+    /// Tight bounds of the curve, containing its ends and extrema
+    fn bounding_box(&self) -> BoundingBox {
+        BoundingBox::from_points(
+            [0., 1.]
+                .iter()
+                .copied()
+                .chain(self.extrema())
+                .map(|t| self.point_at(t)),
+        )
+        .expect("a curve has two ends")
+    }
+
+    /// Returns increasing parameters, from 0 to 1, of points of the curve such that the
+    /// midpoints of the line segments joining them are no further than `tolerance` from the
+    /// point of the curve at the middle parameter
+    fn flatten_parameters(&self, tolerance: f64) -> Vec<f64> {
+        let mut parameters = vec![0.];
+        match self {
+            Curve::Line(..) => parameters.push(1.),
+            _ => self.subdivide(0., 1., tolerance, 0, &mut parameters),
+        }
+        parameters
+    }
+
+    fn subdivide(&self, t0: f64, t1: f64, tolerance: f64, depth: u32, parameters: &mut Vec<f64>) {
+        let middle = (t0 + t1) / 2.;
+        let deviation = distance(
+            self.point_at(middle),
+            lerp(self.point_at(t0), self.point_at(t1), 0.5),
+        );
+        if depth >= MAX_SUBDIVISIONS || (depth >= MIN_SUBDIVISIONS && deviation <= tolerance) {
+            parameters.push(t1);
+        } else {
+            self.subdivide(t0, middle, tolerance, depth + 1, parameters);
+            self.subdivide(middle, t1, tolerance, depth + 1, parameters);
+        }
+    }
+}
+
+impl Commands {
+    /// Returns the drawn segment of every path segment, `None` for moves.
+    /// `End` draws the line closing the subpath.
+    fn curves(&self) -> Vec<Option<Curve>> {
+        self.normalize().normalized_curves()
+    }
+
+    /// Same as `curves` for commands which are already normalized
+    fn normalized_curves(&self) -> Vec<Option<Curve>> {
+        let mut current_point = (0., 0.);
+        let mut subpath_start = (0., 0.);
+        self.iter()
+            .map(|segment| {
+                let start = current_point;
+                let curve = match *segment {
+                    PathSegment::MoveTo(x) => {
+                        subpath_start = x.point;
+                        None
+                    }
+                    PathSegment::LineTo(x) => Some(Curve::Line(start, x.point)),
+                    PathSegment::CubicBezierCurve(x) => Some(Curve::Cubic(
+                        start,
+                        x.control_point_1.unwrap_or(start),
+                        x.control_point_2,
+                        x.point,
+                    )),
+                    PathSegment::QuadraticBezierCurve(x) => Some(Curve::Quadratic(
+                        start,
+                        x.control_point_1.unwrap_or(start),
+                        x.point,
+                    )),
+                    PathSegment::Arc(x) => Some(
+                        CenterArc::from_arc(start, &x)
+                            .map_or(Curve::Line(start, x.point), Curve::Arc),
+                    ),
+                    PathSegment::End(_) => Some(Curve::Line(start, subpath_start)),
+                };
+                current_point = segment.point().unwrap_or(subpath_start);
+                curve
+            })
+            .collect()
+    }
+
+    /// Returns the tight bounds of the path, including curves and arcs between their ends.
+    /// Returns `None` for empty paths, a path made only of moves is bounded by their points.
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        let normalized = self.normalize();
+        let curves = normalized.normalized_curves();
+        let bounding_box = curves
+            .iter()
+            .flatten()
+            .map(Curve::bounding_box)
+            .reduce(|a, b| a.union(&b));
+        // Moves without any drawing after them do not extend the bounds of a drawn path
+        bounding_box.or_else(|| {
+            normalized
+                .iter()
+                .filter_map(|x| match x {
+                    PathSegment::MoveTo(x) => Some(BoundingBox::from_point(x.point)),
+                    _ => None,
+                })
+                .reduce(|a, b| a.union(&b))
+        })
+    }
+
+    /// Returns the length of every segment, zero for moves, curves and arcs being approximated
+    /// by line segments no further than about `tolerance` from them
+    pub fn segment_lengths(&self, tolerance: f64) -> Vec<f64> {
+        self.curves()
+            .iter()
+            .map(|curve| {
+                curve.map_or(0., |curve| {
+                    PathMeasure::measure_curve(&curve, tolerance)
+                        .last()
+                        .map_or(0., |x| x.1)
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the total length of the path, see `segment_lengths`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svg_composer::element::path::builder::PathBuilder;
+    ///
+    /// let circle = PathBuilder::new()
+    ///     .move_to((0., 10.))
+    ///     .arc_to((10., 10.), 0., false, true, (20., 10.))
+    ///     .arc_to((10., 10.), 0., false, true, (0., 10.))
+    ///     .build();
+    /// let length = circle.length(0.001);
+    /// assert!((length - 20. * std::f64::consts::PI).abs() < 0.01);
+    /// ```
+    pub fn length(&self, tolerance: f64) -> f64 {
+        self.segment_lengths(tolerance).iter().sum()
+    }
+
+    /// Returns the point and direction of the segment at `index` for the parameter `t`,
+    /// from 0 at the start of the segment to 1 at its end.
+    /// Returns `None` for moves and indices out of range.
+    pub fn sample_at(&self, index: usize, t: f64) -> Option<PathSample> {
+        let curve = self.curves().get(index).copied().flatten()?;
+        let t = t.clamp(0., 1.);
+        Some(PathSample::new(curve.point_at(t), curve.direction_at(t)))
+    }
+
+    /// Returns the point and direction at `distance` along the path, clamped to the ends of
+    /// the path. Returns `None` for paths which draw nothing.
+    pub fn sample_at_length(&self, distance: f64, tolerance: f64) -> Option<PathSample> {
+        PathMeasure::new(self, tolerance).sample(distance)
+    }
+
+    /// Returns `count` points evenly spaced along the path, including both of its ends
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svg_composer::element::path::builder::PathBuilder;
+    ///
+    /// let commands = PathBuilder::new().move_to((0., 0.)).h(10.).v(10.).build();
+    /// let points: Vec<(f64, f64)> = commands.resample(5, 0.1).iter().map(|x| x.point).collect();
+    /// assert_eq!(points, vec![(0., 0.), (5., 0.), (10., 0.), (10., 5.), (10., 10.)]);
+    /// ```
+    pub fn resample(&self, count: usize, tolerance: f64) -> Vec<PathSample> {
+        let measure = PathMeasure::new(self, tolerance);
+        let step = if count > 1 {
+            measure.length / (count - 1) as f64
+        } else {
+            0.
+        };
+        (0..count)
+            .filter_map(|i| measure.sample(i as f64 * step))
+            .collect()
+    }
+
+    /// Approximates the curves and arcs of the path with line segments, returning the points of
+    /// every subpath. Chords are split until their midpoint is no further than `tolerance` from
+    /// the midpoint of the curve they replace, which estimates but does not bound the distance
+    /// between them.
+    /// Closed subpaths end with their first point, and subpaths drawing nothing are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svg_composer::element::path::builder::PathBuilder;
+    ///
+    /// let commands = PathBuilder::new()
+    ///     .move_to((0., 0.))
+    ///     .h(10.)
+    ///     .v(10.)
+    ///     .close()
+    ///     .move_to((20., 0.))
+    ///     .quad_to((25., 10.), (30., 0.))
+    ///     .build();
+    /// let polylines = commands.flatten(0.1);
+    /// assert_eq!(polylines.len(), 2);
+    /// assert_eq!(polylines[0], vec![(0., 0.), (10., 0.), (10., 10.), (0., 0.)]);
+    /// assert_eq!(polylines[1].last(), Some(&(30., 0.)));
+    /// ```
+    pub fn flatten(&self, tolerance: f64) -> Vec<Vec<(f64, f64)>> {
+        let mut polylines: Vec<Vec<(f64, f64)>> = Vec::new();
+        let mut open = false;
+        let normalized = self.normalize();
+        for (segment, curve) in normalized.iter().zip(normalized.normalized_curves()) {
+            match (segment, curve) {
+                (PathSegment::MoveTo(x), _) => {
+                    polylines.push(vec![x.point]);
+                    open = true;
+                }
+                (_, Some(curve)) => {
+                    if !open {
+                        // Drawing without a move starts at the current point
+                        polylines.push(vec![curve.point_at(0.)]);
+                        open = true;
+                    }
+                    let polyline = polylines.last_mut().expect("a subpath is open");
+                    for t in curve.flatten_parameters(tolerance).into_iter().skip(1) {
+                        let point = curve.point_at(t);
+                        if polyline.last() != Some(&point) {
+                            polyline.push(point);
+                        }
+                    }
+                    if let PathSegment::End(_) = segment {
+                        open = false;
+                    }
+                }
+                _ => {}
+            }
+        }
+        polylines.retain(|x| x.len() > 1);
+        polylines
+    }
+
+    /// Returns the path with the matrix applied to its geometry, so that it draws the same as
+    /// the original path with the matrix as transform.
+    /// Relative segments stay relative, except a leading one which becomes absolute.
+    /// Horizontal and vertical lines become lines as they may not stay aligned with an axis.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use svg_composer::element::path::builder::PathBuilder;
+    /// use svg_composer::geometry::Matrix;
+    ///
+    /// let commands = PathBuilder::new().move_to((1., 0.)).h_rel(2.).build();
+    /// let transformed = commands.transform(&(Matrix::translate(10., 0.) * Matrix::scale(1., -1.)));
+    /// assert_eq!(transformed.to_string(), "M11 0 l2 0");
+    /// ```
+    pub fn transform(&self, matrix: &Matrix) -> Commands {
+        let absolute = self.to_absolute();
+        self.iter()
+            .zip(absolute.iter())
+            .enumerate()
+            .map(|(index, (segment, absolute_segment))| {
+                // The first segment is relative to the origin, which the matrix may move
+                let segment = match (index, segment.coordinate_type()) {
+                    (0, _) | (_, Some(CoordinateType::Absolute)) => absolute_segment,
+                    _ => segment,
+                };
+                let relative = segment.coordinate_type() == Some(CoordinateType::Relative);
+                let map = |point: (f64, f64)| {
+                    if relative {
+                        matrix.apply_vector(point)
+                    } else {
+                        matrix.apply(point)
+                    }
+                };
+                match *segment {
+                    PathSegment::MoveTo(mut x) => {
+                        x.point = map(x.point);
+                        PathSegment::MoveTo(x)
+                    }
+                    PathSegment::LineTo(x) => {
+                        let point = match (relative, x.option) {
+                            (true, LineToOption::Horizontal) => (x.point.0, 0.),
+                            (true, LineToOption::Vertical) => (0., x.point.1),
+                            _ => x.point,
+                        };
+                        PathSegment::LineTo(LineTo {
+                            point: map(point),
+                            option: LineToOption::Default,
+                            coordinate_type: x.coordinate_type,
+                        })
+                    }
+                    PathSegment::CubicBezierCurve(mut x) => {
+                        x.control_point_1 = x.control_point_1.map(map);
+                        x.control_point_2 = map(x.control_point_2);
+                        x.point = map(x.point);
+                        PathSegment::CubicBezierCurve(x)
+                    }
+                    PathSegment::QuadraticBezierCurve(mut x) => {
+                        x.control_point_1 = x.control_point_1.map(map);
+                        x.point = map(x.point);
+                        PathSegment::QuadraticBezierCurve(x)
+                    }
+                    PathSegment::Arc(x) => PathSegment::Arc(x.transform(matrix, map(x.point))),
+                    PathSegment::End(x) => PathSegment::End(x),
+                }
+            })
+            .collect()
+    }
+}
+
+/// The drawn segments of a path with the lengths along them, to find points by distance
+struct PathMeasure {
+    /// Curves with the parameters and distances from their start of the points approximating them
+    curves: Vec<(Curve, Vec<(f64, f64)>)>,
+    length: f64,
+}
+
+impl PathMeasure {
+    fn new(commands: &Commands, tolerance: f64) -> Self {
+        let curves = commands
+            .curves()
+            .into_iter()
+            .flatten()
+            .map(|curve| (curve, PathMeasure::measure_curve(&curve, tolerance)))
+            .collect::<Vec<(Curve, Vec<(f64, f64)>)>>();
+        let length = curves
+            .iter()
+            .map(|(_, table)| table.last().map_or(0., |x| x.1))
+            .sum();
+        PathMeasure { curves, length }
+    }
+
+    fn measure_curve(curve: &Curve, tolerance: f64) -> Vec<(f64, f64)> {
+        let mut length = 0.;
+        let mut previous = curve.point_at(0.);
+        curve
+            .flatten_parameters(tolerance)
+            .into_iter()
+            .map(|t| {
+                let point = curve.point_at(t);
+                length += distance(previous, point);
+                previous = point;
+                (t, length)
+            })
+            .collect()
+    }
+
+    fn sample(&self, distance: f64) -> Option<PathSample> {
+        let mut remaining = distance.clamp(0., self.length);
+        for (index, (curve, table)) in self.curves.iter().enumerate() {
+            let curve_length = table.last().map_or(0., |x| x.1);
+            if remaining > curve_length && index + 1 < self.curves.len() {
+                remaining -= curve_length;
+                continue;
+            }
+            let t = match table.iter().position(|x| x.1 >= remaining) {
+                Some(0) => 0.,
+                Some(i) => {
+                    let (t0, l0) = table[i - 1];
+                    let (t1, l1) = table[i];
+                    t0 + (t1 - t0) * (remaining - l0) / (l1 - l0)
+                }
+                None => 1.,
+            };
+            return Some(PathSample::new(curve.point_at(t), curve.direction_at(t)));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::path::builder::PathBuilder;
+
+    fn assert_close(a: (f64, f64), b: (f64, f64)) {
+        assert!(distance(a, b) < 1e-6, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn should_compute_tight_bounds() {
+        let commands = PathBuilder::new()
+            .move_to((0., 0.))
+            .cubic_to((0., -10.), (10., -10.), (10., 0.))
+            .quad_to((15., 10.), (20., 0.))
+            .arc_to((5., 5.), 0., false, false, (30., 0.))
+            .build();
+        let bounding_box = commands.bounding_box().unwrap();
+        assert!((bounding_box.min_y + 7.5).abs() < 1e-9);
+        assert!((bounding_box.max_y - 5.).abs() < 1e-9);
+        assert!((bounding_box.max_x - 30.).abs() < 1e-9);
+        assert_eq!(bounding_box.min_x, 0.);
+        assert!(Commands::new().bounding_box().is_none());
+
+        let trailing_move = PathBuilder::new()
+            .move_to((0., 0.))
+            .line_to((10., 10.))
+            .move_to((100., 100.))
+            .build();
+        assert_eq!(
+            trailing_move.bounding_box(),
+            Some(BoundingBox::new(0., 0., 10., 10.))
+        );
+        let moves = PathBuilder::new()
+            .move_to((0., 0.))
+            .move_to((10., 5.))
+            .build();
+        assert_eq!(
+            moves.bounding_box(),
+            Some(BoundingBox::new(0., 0., 10., 5.))
+        );
+    }
+
+    #[test]
+    fn should_measure_segments() {
+        let commands = PathBuilder::new()
+            .move_to((0., 0.))
+            .line_to((3., 4.))
+            .cubic_to((3., 4.), (3., 14.), (3., 14.))
+            .quad_to((13., 14.), (23., 14.))
+            .close()
+            .build();
+        let lengths = commands.segment_lengths(0.001);
+        let expected = [0., 5., 10., 20., 23_f64.hypot(14.)];
+        assert_eq!(lengths.len(), expected.len());
+        for (length, expected) in lengths.iter().zip(expected.iter()) {
+            assert!((length - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn should_convert_arcs_to_center_parameterization() {
+        let arc = CenterArc::from_arc(
+            (0., 0.),
+            &Arc::new_circular_ellipse((10., 0.), 1., false, true, CoordinateType::Absolute),
+        )
+        .unwrap();
+        // The radius is scaled up to reach the end point
+        assert_close(arc.center, (5., 0.));
+        assert_close(arc.radius, (5., 5.));
+        assert!((arc.start_angle - PI).abs() < 1e-9);
+        assert!((arc.sweep_angle - PI).abs() < 1e-9);
+        assert_close(arc.point_at(0.5), (5., -5.));
+    }
+
+    #[test]
+    fn should_convert_arcs_to_cubics() {
+        let arc = Arc {
+            radius: (20., 10.),
+            x_axis_rotation: 30.,
+            large_arc_flag: true,
+            sweep_flag: false,
+            point: (15., 5.),
+            coordinate_type: CoordinateType::Absolute,
+        };
+        let center_arc = CenterArc::from_arc((0., 0.), &arc).unwrap();
+        assert!(center_arc.sweep_angle < -PI);
+        let back = center_arc.to_arc();
+        assert_close(back.point, arc.point);
+        assert_eq!(
+            (back.large_arc_flag, back.sweep_flag),
+            (arc.large_arc_flag, arc.sweep_flag)
+        );
+
+        for tolerance in [1., 0.01, 0.0001].iter() {
+            let cubics = arc.to_cubics((0., 0.), *tolerance);
+            assert!(cubics.len() >= 3);
+            assert_close(cubics.last().unwrap().point, (15., 5.));
+            let mut start = (0., 0.);
+            for cubic in cubics.iter() {
+                let curve = Curve::Cubic(
+                    start,
+                    cubic.control_point_1.unwrap(),
+                    cubic.control_point_2,
+                    cubic.point,
+                );
+                for i in 0..=10 {
+                    // Distance to the ellipse, measured in its own coordinate system
+                    let point = curve.point_at(i as f64 / 10.);
+                    let (sin_phi, cos_phi) = center_arc.x_axis_rotation.sin_cos();
+                    let (dx, dy) = (point.0 - center_arc.center.0, point.1 - center_arc.center.1);
+                    let (x, y) = (cos_phi * dx + sin_phi * dy, -sin_phi * dx + cos_phi * dy);
+                    let angle = (y / center_arc.radius.1).atan2(x / center_arc.radius.0);
+                    let closest = center_arc.point_at_angle(angle);
+                    assert!(distance(point, closest) <= 2. * tolerance);
+                }
+                start = cubic.point;
+            }
+        }
+        assert_eq!(
+            Arc::new_circular_ellipse((0., 0.), 5., false, true, CoordinateType::Relative)
+                .to_cubics((3., 3.), 0.1),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn should_flatten_subpaths() {
+        let commands = PathBuilder::new()
+            .move_to((0., 0.))
+            .arc_to((5., 5.), 0., false, true, (10., 0.))
+            .close()
+            .line_to_rel((0., 10.))
+            .move_to((50., 50.))
+            .build();
+        let polylines = commands.flatten(0.01);
+        assert_eq!(polylines.len(), 2);
+        let arc = &polylines[0];
+        assert!(arc.len() > 10);
+        assert_eq!(arc.first(), arc.last());
+        for point in arc[..arc.len() - 2].iter() {
+            assert!((distance(*point, (5., 0.)) - 5.).abs() < 1e-9);
+        }
+        // After closing, drawing continues from the start of the subpath
+        assert_eq!(polylines[1], vec![(0., 0.), (0., 10.)]);
+    }
+
+    #[test]
+    fn should_transform_paths() {
+        let commands = PathBuilder::new()
+            .move_to_rel((10., 10.))
+            .arc_to_rel((20., 10.), 30., true, false, (15., 5.))
+            .cubic_to_rel((0., 5.), (5., 5.), (5., 0.))
+            .smooth_quad_to((40., 20.))
+            .v(0.)
+            .close()
+            .build();
+        let matrices = [
+            Matrix::translate(3., -4.),
+            Matrix::rotate(40., 5., 5.) * Matrix::scale(2., 0.5),
+            Matrix::skew_x(20.) * Matrix::scale(-1., 1.),
+        ];
+        for matrix in matrices.iter() {
+            let transformed = commands.transform(matrix);
+            assert_eq!(
+                transformed
+                    .iter()
+                    .skip(1)
+                    .map(PathSegment::coordinate_type)
+                    .collect::<Vec<_>>(),
+                commands
+                    .iter()
+                    .skip(1)
+                    .map(PathSegment::coordinate_type)
+                    .collect::<Vec<_>>()
+            );
+            let transformed_curves = transformed.curves();
+            for (curve, transformed_curve) in commands.curves().iter().zip(transformed_curves) {
+                if let (Some(curve), Some(transformed_curve)) = (curve, transformed_curve) {
+                    for i in 0..=4 {
+                        let t = i as f64 / 4.;
+                        assert_close(
+                            transformed_curve.point_at(t),
+                            matrix.apply(curve.point_at(t)),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn should_sample_at_length() {
+        let commands = PathBuilder::new()
+            .move_to((0., 10.))
+            .arc_to((10., 10.), 0., false, true, (20., 10.))
+            .line_to((20., 20.))
+            .build();
+        let quarter = commands
+            .sample_at_length(5. * PI, 0.0001)
+            .expect("the path is drawn");
+        assert!(distance(quarter.point, (10., 0.)) < 0.001);
+        assert!(distance(quarter.tangent, (1., 0.)) < 0.001);
+        assert!(distance(quarter.normal, (0., 1.)) < 0.001);
+        let end = commands.sample_at_length(100., 0.0001).unwrap();
+        assert_close(end.point, (20., 20.));
+        assert_close(end.tangent, (0., 1.));
+        assert_close(commands.sample_at(2, 0.5).unwrap().point, (20., 15.));
+        assert!(commands.sample_at(0, 0.5).is_none());
+        assert!(PathBuilder::new()
+            .move_to((1., 1.))
+            .build()
+            .sample_at_length(0., 0.1)
+            .is_none());
+    }
+}
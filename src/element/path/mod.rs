@@ -1,8 +1,13 @@
-use crate::element::attributes::Attributes;
-pub use crate::element::path::command::{Command, Commands};
+use crate::element::attributes::{Attributes, ElementId};
+pub use crate::element::path::command::{Commands, PathSegment};
 use crate::element::Element;
+use crate::geometry::{BoundingBox, Matrix};
 
+pub mod builder;
 pub mod command;
+#[cfg(feature = "font")]
+pub mod font;
+pub mod geometry;
 
 #[derive(Clone)]
 pub struct Path {
@@ -16,35 +21,34 @@ pub struct Path {
 ///
 /// ```
 /// use svg_composer::element::Path;
+/// use svg_composer::element::attributes::ElementId;
 /// use svg_composer::element::path::command::{LineTo, CoordinateType, LineToOption, MoveTo, End};
-/// use svg_composer::element::path::Command;
 ///
-/// let path = Path::new().set_name("my_path").add_commands(vec![
-///     Box::new(MoveTo{
+/// let name = ElementId::from_string("my_path".to_string()).unwrap();
+/// let path = Path::new().set_name(name).add_commands(vec![
+///     MoveTo{
 ///         point: (0.0, 0.0),
 ///         coordinate_type: CoordinateType::Absolute,
-///     }),
-///     Box::new(LineTo{
+///     }.into(),
+///     LineTo{
 ///         point:(10.0, 10.0),
 ///         option: LineToOption::Default,
 ///         coordinate_type: CoordinateType::Absolute,
-///     }),
-///     Box::new(End{}),
+///     }.into(),
+///     End{}.into(),
 /// ]);
 /// ```
 impl Path {
     pub fn new() -> Path {
         let mut attributes = Attributes::default();
-        attributes.d = Some(Commands {
-            commands: Vec::<Box<dyn Command>>::new(),
-        });
+        attributes.d = Some(Commands::new());
         Path { attributes }
     }
-    pub fn set_name(mut self, name: &str) -> Self {
-        self.attributes.id = Some(name.to_string());
+    pub fn set_name(mut self, name: ElementId) -> Self {
+        self.attributes.id = Some(name);
         self
     }
-    pub fn add_commands(mut self, mut commands: Vec<Box<dyn command::Command>>) -> Self {
+    pub fn add_commands(mut self, mut commands: Vec<PathSegment>) -> Self {
         match self.attributes.d {
             Some(ref mut x) => {
                 x.commands.append(&mut commands);
@@ -55,19 +59,96 @@ impl Path {
         }
         self
     }
-    pub fn add_command(mut self, mut command: Box<dyn command::Command>) -> Self {
+    pub fn add_command<S: Into<PathSegment>>(mut self, command: S) -> Self {
         match self.attributes.d {
             Some(ref mut x) => {
-                x.commands.push(command);
+                x.push(command);
             }
             None => {
                 self.attributes.d = Some(Commands {
-                    commands: vec![command],
+                    commands: vec![command.into()],
                 });
             }
         }
         self
     }
+    /// Replaces the commands of the path, typically built with a `PathBuilder`
+    pub fn set_commands(mut self, commands: Commands) -> Self {
+        self.attributes.d = Some(commands);
+        self
+    }
+    pub fn commands(&self) -> Option<&Commands> {
+        self.attributes.d.as_ref()
+    }
+    pub fn commands_mut(&mut self) -> Option<&mut Commands> {
+        self.attributes.d.as_mut()
+    }
+    /// Returns a path drawing `commands` with the attributes of a shape, without the
+    /// attributes describing the geometry of the shape
+    pub(crate) fn from_shape(mut attributes: Attributes, commands: Commands) -> Path {
+        for size in [
+            &mut attributes.cx,
+            &mut attributes.cy,
+            &mut attributes.radius,
+            &mut attributes.x,
+            &mut attributes.y,
+            &mut attributes.width,
+            &mut attributes.height,
+            &mut attributes.rx,
+            &mut attributes.ry,
+            &mut attributes.x1,
+            &mut attributes.y1,
+            &mut attributes.x2,
+            &mut attributes.y2,
+        ] {
+            *size = None;
+        }
+        attributes.d = Some(commands);
+        Path { attributes }
+    }
+    /// Applies the transform of the path to its commands and removes it.
+    /// Paths can draw any transformed geometry, so unlike for other shapes this cannot fail.
+    /// Lengths which are not coordinates, like the stroke width, are left unchanged.
+    pub fn bake_transform(mut self) -> Path {
+        if let Some(transforms) = self.attributes.transform.take() {
+            let matrix = Matrix::from_transforms(&transforms);
+            self.attributes.d = self.attributes.d.map(|x| x.transform(&matrix));
+        }
+        self
+    }
+    /// Approximates the path with polylines, see `Commands::flatten`
+    pub fn to_polylines(&self, tolerance: f64) -> Vec<Vec<(f64, f64)>> {
+        self.attributes
+            .d
+            .as_ref()
+            .map_or_else(Vec::new, |x| x.flatten(tolerance))
+    }
+}
+
+/// Shapes which can be drawn with a path, to use path operations like transforms or
+/// flattening on any of them
+///
+/// # Examples
+///
+/// ```
+/// use svg_composer::element::path::ToPath;
+/// use svg_composer::element::rect::Rectangle;
+///
+/// let path = Rectangle::new().set_pos((0., 0.)).set_size(10., 5.).to_path().unwrap();
+/// assert_eq!(path.commands().unwrap().to_string(), "M0 0 H10 V5 H0 Z");
+/// ```
+pub trait ToPath {
+    /// Returns a path drawing the same geometry with the other attributes of the shape.
+    /// Shapes which are not rendered, like circles with a zero radius or rectangles with a zero
+    /// width, give a path without commands which is not rendered either.
+    /// Fails if the geometry depends on the viewport, like sizes in percentages.
+    fn to_path(&self) -> Result<Path, String>;
+}
+
+impl ToPath for Path {
+    fn to_path(&self) -> Result<Path, String> {
+        Ok(self.clone())
+    }
 }
 
 impl Element for Path {
@@ -80,4 +161,12 @@ impl Element for Path {
     fn tag_name(&self) -> String {
         "path".to_string()
     }
+
+    fn local_bounding_box(&self) -> Option<BoundingBox> {
+        self.attributes.d.as_ref()?.bounding_box()
+    }
+
+    fn transformed_bounding_box(&self, matrix: &Matrix) -> Option<BoundingBox> {
+        self.attributes.d.as_ref()?.transform(matrix).bounding_box()
+    }
 }
@@ -1,5 +1,8 @@
 use crate::element::attributes::{Attributes, Size, ToSize};
-use crate::element::Element;
+use crate::element::path::builder::PathBuilder;
+use crate::element::path::{Commands, Path, ToPath};
+use crate::element::{length_or, shape_bounding_box, Element};
+use crate::geometry::{BoundingBox, Matrix};
 
 #[derive(Clone)]
 pub struct Rectangle {
@@ -36,6 +39,113 @@ impl Rectangle {
         self.attributes.ry = Some(ry.to_size());
         self
     }
+    pub fn pos(&self) -> Option<(Size, Size)> {
+        self.attributes.x.zip(self.attributes.y)
+    }
+    pub fn size(&self) -> Option<(Size, Size)> {
+        self.attributes.width.zip(self.attributes.height)
+    }
+    pub fn rounding(&self) -> Option<(Size, Size)> {
+        self.attributes.rx.zip(self.attributes.ry)
+    }
+    /// Returns the radii of the corners in user units, a missing radius taking the value of
+    /// the other one. Returns `None` if a radius is a percentage.
+    fn radii(&self) -> Option<(f64, f64)> {
+        Some(match (self.attributes.rx, self.attributes.ry) {
+            (Some(rx), Some(ry)) => (rx.as_length()?, ry.as_length()?),
+            (Some(rx), None) => (rx.as_length()?, rx.as_length()?),
+            (None, Some(ry)) => (ry.as_length()?, ry.as_length()?),
+            (None, None) => (0., 0.),
+        })
+    }
+    /// Returns the commands drawing the rectangle clockwise from its top left corner, with arcs
+    /// for rounded corners. A missing radius takes the value of the other one, and radii are
+    /// limited to half the size of the rectangle like when rendering it.
+    /// Returns `None` if a size is a percentage.
+    pub(crate) fn to_commands(&self) -> Option<Commands> {
+        let x = length_or(self.attributes.x, 0.)?;
+        let y = length_or(self.attributes.y, 0.)?;
+        let width = length_or(self.attributes.width, 0.)?;
+        let height = length_or(self.attributes.height, 0.)?;
+        let (rx, ry) = self.radii()?;
+        if width <= 0. || height <= 0. {
+            return Some(Commands::new());
+        }
+        let (rx, ry) = (rx.clamp(0., width / 2.), ry.clamp(0., height / 2.));
+        let commands = if rx > 0. && ry > 0. {
+            let mut builder = PathBuilder::new().move_to((x + rx, y));
+            let corner =
+                |builder: PathBuilder, point| builder.arc_to((rx, ry), 0., false, true, point);
+            // Sides are skipped when the corners take the whole width or height
+            let (has_width, has_height) = (width > 2. * rx, height > 2. * ry);
+            if has_width {
+                builder = builder.h(x + width - rx);
+            }
+            builder = corner(builder, (x + width, y + ry));
+            if has_height {
+                builder = builder.v(y + height - ry);
+            }
+            builder = corner(builder, (x + width - rx, y + height));
+            if has_width {
+                builder = builder.h(x + rx);
+            }
+            builder = corner(builder, (x, y + height - ry));
+            if has_height {
+                builder = builder.v(y + ry);
+            }
+            corner(builder, (x + rx, y))
+        } else {
+            // A single zero radius makes all corners sharp
+            PathBuilder::new()
+                .move_to((x, y))
+                .h(x + width)
+                .v(y + height)
+                .h(x)
+        };
+        Some(commands.close().build())
+    }
+    /// Applies the transform of the rectangle to its geometry and removes it.
+    /// The rectangle is kept if the transform only translates and scales it, it is converted
+    /// to a path otherwise, hence the boxed result. Lengths which are not coordinates, like the
+    /// stroke width, are left unchanged. Fails if a size of the rectangle is a percentage.
+    pub fn bake_transform(mut self) -> Result<Box<dyn Element>, String> {
+        let transforms = match self.attributes.transform.take() {
+            Some(transforms) => transforms,
+            None => return Ok(Box::new(self)),
+        };
+        let matrix = Matrix::from_transforms(&transforms);
+        let commands = self
+            .to_commands()
+            .ok_or("Rectangles with percentage sizes cannot be transformed")?;
+        if !matrix.is_axis_aligned() {
+            return Ok(Box::new(Path::from_shape(
+                self.attributes,
+                commands.transform(&matrix),
+            )));
+        }
+        // Sizes and radii are lengths as the commands were computed
+        let (bounding_box, (rx, ry)) = match (self.local_bounding_box(), self.radii()) {
+            (Some(bounding_box), Some(radii)) => (bounding_box.transform(&matrix), radii),
+            _ => return Err("Rectangles with percentage sizes cannot be transformed".to_string()),
+        };
+        if self.attributes.rx.is_some() || self.attributes.ry.is_some() {
+            // Both radii are set as a missing one would follow the scale of the other axis
+            self = self.set_rounding(rx * matrix.a.abs(), ry * matrix.d.abs());
+        }
+        Ok(Box::new(
+            self.set_pos((bounding_box.min_x, bounding_box.min_y))
+                .set_size(bounding_box.width(), bounding_box.height()),
+        ))
+    }
+}
+
+impl ToPath for Rectangle {
+    fn to_path(&self) -> Result<Path, String> {
+        let commands = self
+            .to_commands()
+            .ok_or("Rectangles with percentage sizes cannot be converted to paths")?;
+        Ok(Path::from_shape(self.attributes.clone(), commands))
+    }
 }
 
 impl Element for Rectangle {
@@ -50,4 +160,17 @@ impl Element for Rectangle {
     fn tag_name(&self) -> String {
         "rect".to_string()
     }
+
+    fn local_bounding_box(&self) -> Option<BoundingBox> {
+        Some(BoundingBox::new(
+            length_or(self.attributes.x, 0.)?,
+            length_or(self.attributes.y, 0.)?,
+            length_or(self.attributes.width, 0.)?,
+            length_or(self.attributes.height, 0.)?,
+        ))
+    }
+
+    fn transformed_bounding_box(&self, matrix: &Matrix) -> Option<BoundingBox> {
+        shape_bounding_box(self.to_commands(), self.local_bounding_box(), matrix)
+    }
 }
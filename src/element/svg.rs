@@ -0,0 +1,108 @@
+use crate::element::attributes::{Attributes, PreserveAspectRatio, Size, ToSize};
+use crate::element::{length_or, Element};
+use crate::geometry::BoundingBox;
+
+/// SVG element nested in a document, a container establishing a new viewport with its own
+/// coordinate system defined by its view box
+/// https://developer.mozilla.org/en-US/docs/Web/SVG/Element/svg
+///
+/// # Examples
+///
+/// ```
+/// use svg_composer::element::circle::Circle;
+/// use svg_composer::element::svg::Svg;
+/// use svg_composer::element::Element;
+///
+/// // Draws a circle defined in a 10x10 coordinate system in a 100x100 square
+/// let panel = Svg::new()
+///     .set_pos((20., 20.))
+///     .set_size(100., 100.)
+///     .set_view_box([0., 0., 10., 10.])
+///     .add_element(Box::new(Circle::new().set_pos((5., 5.)).set_radius(4.)));
+/// ```
+#[derive(Clone, Default)]
+pub struct Svg {
+    attributes: Attributes,
+    elements: Vec<Box<dyn Element>>,
+}
+
+impl Svg {
+    pub fn new() -> Self {
+        Svg::default()
+    }
+    pub fn set_pos<I>(mut self, pos: (I, I)) -> Self
+    where
+        I: ToSize,
+    {
+        self.attributes.x = Some(pos.0.to_size());
+        self.attributes.y = Some(pos.1.to_size());
+        self
+    }
+    pub fn set_size<I>(mut self, width: I, height: I) -> Self
+    where
+        I: ToSize,
+    {
+        self.attributes.width = Some(width.to_size());
+        self.attributes.height = Some(height.to_size());
+        self
+    }
+    /// Set the rectangle (minx, miny, width, height) of the coordinate system of the children
+    /// which is mapped to the viewport
+    pub fn set_view_box(mut self, view_box: [f32; 4]) -> Self {
+        self.attributes.view_box = Some(view_box);
+        self
+    }
+    pub fn set_preserve_aspect_ratio(mut self, value: PreserveAspectRatio) -> Self {
+        self.attributes.preserve_aspect_ratio = Some(value);
+        self
+    }
+    pub fn add_element(mut self, element: Box<dyn Element>) -> Self {
+        self.elements.push(element);
+        self
+    }
+    pub fn add_elements(mut self, mut elements: Vec<Box<dyn Element>>) -> Self {
+        self.elements.append(&mut elements);
+        self
+    }
+    pub fn pos(&self) -> Option<(Size, Size)> {
+        self.attributes.x.zip(self.attributes.y)
+    }
+    pub fn size(&self) -> Option<(Size, Size)> {
+        self.attributes.width.zip(self.attributes.height)
+    }
+    pub fn view_box(&self) -> Option<[f32; 4]> {
+        self.attributes.view_box
+    }
+}
+
+impl Element for Svg {
+    fn get_mut_attributes(&mut self) -> &mut Attributes {
+        &mut self.attributes
+    }
+
+    fn get_attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+
+    fn tag_name(&self) -> String {
+        "svg".to_string()
+    }
+
+    /// The viewport of the element, to which its content is clipped
+    fn local_bounding_box(&self) -> Option<BoundingBox> {
+        Some(BoundingBox::new(
+            length_or(self.attributes.x, 0.)?,
+            length_or(self.attributes.y, 0.)?,
+            self.attributes.width?.as_length()?,
+            self.attributes.height?.as_length()?,
+        ))
+    }
+
+    fn get_children(&self) -> Option<&Vec<Box<dyn Element>>> {
+        Some(&self.elements)
+    }
+
+    fn get_mut_children(&mut self) -> Option<&mut Vec<Box<dyn Element>>> {
+        Some(&mut self.elements)
+    }
+}
@@ -1,5 +1,9 @@
 use crate::element::attributes::{Attributes, LengthAdjust, Size, ToSize};
-use crate::element::Element;
+use crate::element::{length_or, Element};
+use crate::geometry::BoundingBox;
+
+/// Font size used by browsers when none is set, in user units
+static DEFAULT_FONT_SIZE: f64 = 16.;
 
 #[derive(Clone)]
 pub struct Text {
@@ -47,6 +51,18 @@ impl Text {
         self.attributes.length_adjust = Some(adjust);
         self
     }
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+    pub fn pos(&self) -> Option<(Size, Size)> {
+        self.attributes.x.zip(self.attributes.y)
+    }
+    pub fn relative_pos(&self) -> Option<(Size, Size)> {
+        self.attributes.dx.zip(self.attributes.dy)
+    }
+    pub fn length(&self) -> Option<Size> {
+        self.attributes.text_length
+    }
 }
 
 impl Element for Text {
@@ -62,6 +78,22 @@ impl Element for Text {
         "text".to_string()
     }
 
+    /// Approximate box of the text, assuming characters are 0.6 em wide and the baseline is
+    /// at 0.8 em from the top, since the metrics of the font are not known
+    fn local_bounding_box(&self) -> Option<BoundingBox> {
+        let font_size = length_or(
+            self.attributes.style.as_ref().and_then(|x| x.font_size()),
+            DEFAULT_FONT_SIZE,
+        )?;
+        let x = length_or(self.attributes.x, 0.)? + length_or(self.attributes.dx, 0.)?;
+        let y = length_or(self.attributes.y, 0.)? + length_or(self.attributes.dy, 0.)?;
+        let width = length_or(
+            self.attributes.text_length,
+            self.value.chars().count() as f64 * 0.6 * font_size,
+        )?;
+        Some(BoundingBox::new(x, y - 0.8 * font_size, width, font_size))
+    }
+
     fn tag_content(&self) -> Option<String> {
         Some(self.value.clone())
     }
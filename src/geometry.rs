@@ -0,0 +1,281 @@
+use crate::element::attributes::Transform;
+use std::ops::Mul;
+
+/// An axis-aligned rectangle containing some geometry, in user units
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BoundingBox {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+
+impl BoundingBox {
+    /// Returns the box of the rectangle at (x, y) of the given size
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        BoundingBox {
+            min_x: x.min(x + width),
+            min_y: y.min(y + height),
+            max_x: x.max(x + width),
+            max_y: y.max(y + height),
+        }
+    }
+    pub fn from_point(point: (f64, f64)) -> Self {
+        BoundingBox {
+            min_x: point.0,
+            min_y: point.1,
+            max_x: point.0,
+            max_y: point.1,
+        }
+    }
+    /// Returns the smallest box containing all the points, `None` if there are none
+    pub fn from_points<I: IntoIterator<Item = (f64, f64)>>(points: I) -> Option<Self> {
+        points
+            .into_iter()
+            .map(BoundingBox::from_point)
+            .reduce(|a, b| a.union(&b))
+    }
+    pub fn width(&self) -> f64 {
+        self.max_x - self.min_x
+    }
+    pub fn height(&self) -> f64 {
+        self.max_y - self.min_y
+    }
+    pub fn center(&self) -> (f64, f64) {
+        (
+            (self.min_x + self.max_x) / 2.,
+            (self.min_y + self.max_y) / 2.,
+        )
+    }
+    /// Returns the (minx, miny, width, height) array used for view boxes
+    pub fn to_view_box(&self) -> [f32; 4] {
+        [
+            self.min_x as f32,
+            self.min_y as f32,
+            self.width() as f32,
+            self.height() as f32,
+        ]
+    }
+    /// Returns the smallest box containing both boxes
+    pub fn union(&self, other: &BoundingBox) -> Self {
+        BoundingBox {
+            min_x: self.min_x.min(other.min_x),
+            min_y: self.min_y.min(other.min_y),
+            max_x: self.max_x.max(other.max_x),
+            max_y: self.max_y.max(other.max_y),
+        }
+    }
+    /// Returns the box grown by `margin` on every side
+    pub fn expand(&self, margin: f64) -> Self {
+        BoundingBox {
+            min_x: self.min_x - margin,
+            min_y: self.min_y - margin,
+            max_x: self.max_x + margin,
+            max_y: self.max_y + margin,
+        }
+    }
+    /// Returns the smallest box containing the transformed corners of the box, which is larger
+    /// than the transformed geometry itself for rotations and skews unless the geometry is the
+    /// rectangle itself. See `Element::transformed_bounding_box` for tight bounds.
+    pub fn transform(&self, matrix: &Matrix) -> Self {
+        BoundingBox::from_points(
+            [
+                (self.min_x, self.min_y),
+                (self.max_x, self.min_y),
+                (self.max_x, self.max_y),
+                (self.min_x, self.max_y),
+            ]
+            .iter()
+            .map(|x| matrix.apply(*x)),
+        )
+        .expect("a box has four corners")
+    }
+}
+
+/// A 2D affine transformation matrix, mapping (x, y) to (a * x + c * y + e, b * x + d * y + f)
+/// like the `matrix` transform of SVG
+///
+/// # Examples
+///
+/// ```
+/// use svg_composer::geometry::Matrix;
+///
+/// // Transformations are applied from right to left, here scaling first
+/// let matrix = Matrix::translate(10., 0.) * Matrix::scale(2., 2.);
+/// assert_eq!(matrix.apply((1., 1.)), (12., 2.));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Matrix {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+impl Matrix {
+    pub fn identity() -> Self {
+        Matrix {
+            a: 1.,
+            b: 0.,
+            c: 0.,
+            d: 1.,
+            e: 0.,
+            f: 0.,
+        }
+    }
+    pub fn translate(x: f64, y: f64) -> Self {
+        Matrix {
+            e: x,
+            f: y,
+            ..Matrix::identity()
+        }
+    }
+    pub fn scale(x: f64, y: f64) -> Self {
+        Matrix {
+            a: x,
+            d: y,
+            ..Matrix::identity()
+        }
+    }
+    /// Rotation by an angle in degrees around the point (cx, cy)
+    pub fn rotate(angle: f64, cx: f64, cy: f64) -> Self {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let rotation = Matrix {
+            a: cos,
+            b: sin,
+            c: -sin,
+            d: cos,
+            ..Matrix::identity()
+        };
+        Matrix::translate(cx, cy) * rotation * Matrix::translate(-cx, -cy)
+    }
+    /// Skew along the x axis by an angle in degrees
+    pub fn skew_x(angle: f64) -> Self {
+        Matrix {
+            c: angle.to_radians().tan(),
+            ..Matrix::identity()
+        }
+    }
+    /// Skew along the y axis by an angle in degrees
+    pub fn skew_y(angle: f64) -> Self {
+        Matrix {
+            b: angle.to_radians().tan(),
+            ..Matrix::identity()
+        }
+    }
+    /// Returns the matrix of a list of transforms as found in a `transform` attribute
+    pub fn from_transforms(transforms: &[Transform]) -> Self {
+        transforms
+            .iter()
+            .fold(Matrix::identity(), |matrix, x| matrix * Matrix::from(*x))
+    }
+    pub fn apply(&self, point: (f64, f64)) -> (f64, f64) {
+        (
+            self.a * point.0 + self.c * point.1 + self.e,
+            self.b * point.0 + self.d * point.1 + self.f,
+        )
+    }
+    /// Applies the matrix to a vector, ignoring the translation
+    pub fn apply_vector(&self, vector: (f64, f64)) -> (f64, f64) {
+        (
+            self.a * vector.0 + self.c * vector.1,
+            self.b * vector.0 + self.d * vector.1,
+        )
+    }
+    pub fn determinant(&self) -> f64 {
+        self.a * self.d - self.b * self.c
+    }
+    /// Returns true if the matrix maps circles to circles: a combination of rotations,
+    /// reflections, uniform scales and translations, up to rounding errors
+    pub fn is_similarity(&self) -> bool {
+        (nearly_equal(self.a, self.d) && nearly_equal(self.b, -self.c))
+            || (nearly_equal(self.a, -self.d) && nearly_equal(self.b, self.c))
+    }
+    /// Returns true if the matrix maps axis-aligned rectangles to axis-aligned rectangles
+    /// without swapping the axes: a combination of scales and translations, up to rounding
+    /// errors
+    pub fn is_axis_aligned(&self) -> bool {
+        nearly_equal(self.b, 0.) && nearly_equal(self.c, 0.)
+    }
+    /// Returns the largest factor by which the matrix stretches a vector, its largest singular
+    /// value
+    pub fn max_scale(&self) -> f64 {
+        let p = self.a * self.a + self.b * self.b;
+        let q = self.c * self.c + self.d * self.d;
+        let r = self.a * self.c + self.b * self.d;
+        ((p + q) / 2. + (((p - q) / 2.).powi(2) + r * r).sqrt()).sqrt()
+    }
+}
+
+/// Compares coefficients of matrices, which are rarely exact after rotations by right angles
+fn nearly_equal(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-12
+}
+
+impl Mul for Matrix {
+    type Output = Matrix;
+
+    /// Returns the matrix applying `rhs` first and then `self`
+    fn mul(self, rhs: Matrix) -> Matrix {
+        Matrix {
+            a: self.a * rhs.a + self.c * rhs.b,
+            b: self.b * rhs.a + self.d * rhs.b,
+            c: self.a * rhs.c + self.c * rhs.d,
+            d: self.b * rhs.c + self.d * rhs.d,
+            e: self.a * rhs.e + self.c * rhs.f + self.e,
+            f: self.b * rhs.e + self.d * rhs.f + self.f,
+        }
+    }
+}
+
+impl From<Transform> for Matrix {
+    fn from(transform: Transform) -> Self {
+        match transform {
+            Transform::Matrix(a, b, c, d, e, f) => Matrix { a, b, c, d, e, f },
+            Transform::Translate(x, y) => Matrix::translate(x, y),
+            Transform::Scale(x, y) => Matrix::scale(x, y),
+            Transform::Rotate(angle, cx, cy) => Matrix::rotate(angle, cx, cy),
+            Transform::SkewX(angle) => Matrix::skew_x(angle),
+            Transform::SkewY(angle) => Matrix::skew_y(angle),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_compose_transforms() {
+        let matrix = Matrix::from_transforms(&[
+            Transform::Translate(10., 20.),
+            Transform::Rotate(90., 0., 0.),
+            Transform::Scale(2., 1.),
+        ]);
+        let point = matrix.apply((1., 1.));
+        assert!((point.0 - 9.).abs() < 1e-9 && (point.1 - 22.).abs() < 1e-9);
+        let rotated = Matrix::rotate(180., 5., 5.).apply((0., 0.));
+        assert!((rotated.0 - 10.).abs() < 1e-9 && (rotated.1 - 10.).abs() < 1e-9);
+        let skew = Matrix::rotate(30., 0., 0.) * Matrix::scale(3., -2.);
+        assert!((skew.max_scale() - 3.).abs() < 1e-9);
+        let half_turn = Matrix::rotate(90., 0., 0.) * Matrix::rotate(90., 0., 0.);
+        assert!(half_turn.is_axis_aligned() && half_turn.is_similarity());
+        assert!(!Matrix::rotate(90., 0., 0.).is_axis_aligned());
+        assert!(!Matrix::scale(2., 1.).is_similarity());
+    }
+
+    #[test]
+    fn should_transform_bounding_boxes() {
+        let bounding_box = BoundingBox::new(0., 0., 10., 20.);
+        assert_eq!(
+            bounding_box.transform(&Matrix::scale(-1., 0.5)),
+            BoundingBox::new(-10., 0., 10., 10.)
+        );
+        assert_eq!(
+            bounding_box.union(&BoundingBox::from_point((-5., 30.))),
+            BoundingBox::new(-5., 0., 15., 30.)
+        );
+    }
+}
@@ -1,5 +1,10 @@
 pub mod document;
 pub mod element;
+pub mod geometry;
+pub mod metadata;
+pub mod namespace;
+pub mod stylesheet;
+pub mod visitor;
 pub use document::Document;
 
 #[cfg(test)]
@@ -11,22 +16,22 @@ mod tests {
 
     #[test]
     fn should_render_path() {
-        let mut path_commands: Vec<Box<dyn path::Command>> = vec![
-            Box::new(path::command::MoveTo {
+        let mut path_commands: Vec<path::PathSegment> = vec![
+            path::PathSegment::MoveTo(path::command::MoveTo {
                 point: (0_f64, 0_f64),
                 coordinate_type: path::command::CoordinateType::Absolute,
             }),
-            Box::new(path::command::LineTo {
+            path::PathSegment::LineTo(path::command::LineTo {
                 point: (10.0, 0.0),
                 option: path::command::LineToOption::Default,
                 coordinate_type: path::command::CoordinateType::Relative,
             }),
-            Box::new(path::command::LineTo {
+            path::PathSegment::LineTo(path::command::LineTo {
                 point: (0.0, 10.0),
                 option: path::command::LineToOption::Default,
                 coordinate_type: path::command::CoordinateType::Relative,
             }),
-            Box::new(path::command::LineTo {
+            path::PathSegment::LineTo(path::command::LineTo {
                 point: (-10.0, 0.0),
                 option: path::command::LineToOption::Default,
                 coordinate_type: path::command::CoordinateType::Relative,
@@ -34,10 +39,178 @@ mod tests {
         ];
         let paths: Vec<Box<dyn Element>> = vec![Box::new(
             element::path::Path::new()
-                .set_name("path_1")
+                .set_name(
+                    element::attributes::ElementId::from_string("path_1".to_string()).unwrap(),
+                )
                 .add_commands(path_commands),
         )];
         let document = document::Document::new(paths, None);
         assert_eq!(document.render(), "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" viewBox=\"0 0 100 100\">\n<path id=\"path_1\" d=\"M0 0 l10 0 l0 10 l-10 0\"/>\n</svg>\n");
     }
+
+    #[test]
+    fn should_downcast_elements_of_document() {
+        let elements: Vec<Box<dyn Element>> =
+            vec![
+                Box::new(
+                    element::circle::Circle::new()
+                        .set_pos((1., 2.))
+                        .set_radius(3.),
+                ),
+                Box::new(element::rect::Rectangle::new().set_id(
+                    element::attributes::ElementId::from_string("rect".to_string()).unwrap(),
+                )),
+            ];
+        let mut document = document::Document::new(elements, None);
+
+        let circle = document
+            .iter()
+            .find_map(|x| x.downcast_ref::<element::circle::Circle>())
+            .unwrap();
+        let (cx, cy) = circle.pos().unwrap();
+        assert_eq!((cx.as_length(), cy.as_length()), (Some(1.), Some(2.)));
+        assert!(document
+            .get_by_id("rect")
+            .unwrap()
+            .is::<element::rect::Rectangle>());
+        assert!(document
+            .get_by_id_mut("rect")
+            .unwrap()
+            .downcast_mut::<element::circle::Circle>()
+            .is_none());
+    }
+
+    #[test]
+    fn should_bake_transforms_into_shapes() {
+        use element::attributes::Transform;
+
+        let circle = element::circle::Circle::new()
+            .set_pos((10., 0.))
+            .set_radius(5.)
+            .set_transform(vec![
+                Transform::Rotate(90., 0., 0.),
+                Transform::Scale(-2., 2.),
+            ])
+            .bake_transform()
+            .unwrap();
+        let circle = circle.downcast_ref::<element::circle::Circle>().unwrap();
+        let (cx, cy) = circle.pos().unwrap();
+        assert!(cx.as_length().unwrap().abs() < 1e-9);
+        assert!((cy.as_length().unwrap() + 20.).abs() < 1e-9);
+        assert_eq!(circle.radius().unwrap().as_length(), Some(10.));
+        assert!(circle.get_attributes().transform.is_none());
+
+        let ellipse = element::circle::Circle::new()
+            .set_radius(5.)
+            .set_transform(vec![Transform::Scale(2., 1.)])
+            .bake_transform()
+            .unwrap();
+        assert_eq!(
+            ellipse.to_string(),
+            "<path d=\"M10 0 A10 5 0 0 1 -10 0 A10 5 0 0 1 10 0 Z\"/>"
+        );
+
+        let rectangle = element::rect::Rectangle::new()
+            .set_pos((1., 1.))
+            .set_size(2., 4.)
+            .set_rounding(1., 1.)
+            .set_transform(vec![Transform::Scale(-1., 2.)])
+            .bake_transform()
+            .unwrap();
+        assert_eq!(
+            rectangle.to_string(),
+            "<rect x=\"-3\" y=\"2\" rx=\"1\" ry=\"2\" width=\"2\" height=\"8\"/>"
+        );
+        let mut rounded = element::rect::Rectangle::new()
+            .set_size(4., 4.)
+            .set_transform(vec![Transform::Scale(2., 1.)]);
+        rounded.get_mut_attributes().ry = Some(element::attributes::Size::from_length(1.));
+        assert_eq!(
+            rounded.bake_transform().unwrap().to_string(),
+            "<rect x=\"0\" y=\"0\" rx=\"2\" ry=\"1\" width=\"8\" height=\"4\"/>"
+        );
+        let mut flat = element::rect::Rectangle::new()
+            .set_size(0., 10.)
+            .set_transform(vec![Transform::Scale(2., 2.)]);
+        flat.get_mut_attributes().rx = Some(element::attributes::Size::from_percentage(10.));
+        assert!(flat.bake_transform().is_err());
+        let half_turn = element::rect::Rectangle::new()
+            .set_size(2., 4.)
+            .set_transform(vec![Transform::Rotate(180., 0., 0.)])
+            .bake_transform()
+            .unwrap();
+        assert!(half_turn.is::<element::rect::Rectangle>());
+        let rotated = element::rect::Rectangle::new()
+            .set_size(2., 4.)
+            .set_transform(vec![Transform::Rotate(90., 0., 0.)])
+            .bake_transform()
+            .unwrap();
+        assert!(rotated.is::<path::Path>());
+        let bounding_box = rotated.bounding_box().unwrap();
+        assert!((bounding_box.min_x + 4.).abs() < 1e-9 && (bounding_box.max_y - 2.).abs() < 1e-9);
+
+        let line = element::line::Line::new()
+            .set_point_1((1., 1.))
+            .set_point_2((2., 3.))
+            .set_transform(vec![Transform::Translate(1., -1.)])
+            .bake_transform()
+            .unwrap();
+        let (x, y) = line.point_2().unwrap();
+        assert_eq!((x.as_length(), y.as_length()), (Some(3.), Some(2.)));
+    }
+
+    #[test]
+    fn should_convert_shapes_to_paths() {
+        use element::path::ToPath;
+
+        let circle = element::circle::Circle::new()
+            .set_pos((5., 5.))
+            .set_radius(2.)
+            .set_id(element::attributes::ElementId::from_string("dot".to_string()).unwrap());
+        let path: Box<dyn Element> = Box::new(circle.to_path().unwrap());
+        assert_eq!(
+            path.to_string(),
+            "<path id=\"dot\" d=\"M7 5 A2 2 0 0 1 3 5 A2 2 0 0 1 7 5 Z\"/>"
+        );
+        assert_eq!(path.bounding_box(), circle.bounding_box());
+
+        let rectangle = element::rect::Rectangle::new()
+            .set_pos((0., 0.))
+            .set_size(10., 4.)
+            .set_rounding(8., 1.);
+        assert_eq!(
+            rectangle.to_path().unwrap().commands().unwrap().to_string(),
+            "M5 0 A5 1 0 0 1 10 1 V3 A5 1 0 0 1 5 4 A5 1 0 0 1 0 3 V1 A5 1 0 0 1 5 0 Z"
+        );
+        assert_eq!(
+            rectangle.to_path().unwrap().bounding_box(),
+            rectangle.bounding_box()
+        );
+
+        let sharp = element::rect::Rectangle::new()
+            .set_size(10., 10.)
+            .set_rounding(5., 0.);
+        assert_eq!(
+            sharp.to_path().unwrap().commands().unwrap().to_string(),
+            "M0 0 H10 V10 H0 Z"
+        );
+
+        let line = element::line::Line::new()
+            .set_point_1((1., 2.))
+            .set_point_2((3., 4.));
+        assert_eq!(
+            line.to_path().unwrap().commands().unwrap().to_string(),
+            "M1 2 L3 4"
+        );
+        assert!(element::circle::Circle::new()
+            .to_path()
+            .unwrap()
+            .commands()
+            .unwrap()
+            .is_empty());
+        assert!(element::circle::Circle::new()
+            .set_radius(element::attributes::Size::from_percentage(50.))
+            .to_path()
+            .is_err());
+    }
 }
@@ -0,0 +1,167 @@
+use crate::element::attributes::escape_xml;
+use crate::namespace::Namespace;
+use std::fmt;
+
+/// Authoring and licensing information about a document, rendered as a `<metadata>` element
+/// containing an RDF description using Dublin Core terms, as written by Inkscape
+/// https://developer.mozilla.org/en-US/docs/Web/SVG/Element/metadata
+///
+/// # Examples
+///
+/// ```
+/// use svg_composer::metadata::Metadata;
+///
+/// let metadata = Metadata::new()
+///     .set_title("Composition #12")
+///     .set_creator("Jane Doe")
+///     .set_date("2020-10-25")
+///     .set_license("https://creativecommons.org/licenses/by-nc/4.0/")
+///     .add_keyword("generative");
+/// ```
+#[derive(Clone, Default)]
+pub struct Metadata {
+    title: Option<String>,
+    creator: Option<String>,
+    date: Option<String>,
+    license: Option<String>,
+    description: Option<String>,
+    keywords: Vec<String>,
+    source: Option<String>,
+}
+
+impl Metadata {
+    pub fn new() -> Self {
+        Metadata::default()
+    }
+    pub fn set_title(mut self, value: &str) -> Self {
+        self.title = Some(value.to_string());
+        self
+    }
+    pub fn set_creator(mut self, value: &str) -> Self {
+        self.creator = Some(value.to_string());
+        self
+    }
+    /// Set the date of creation, preferably formatted following ISO 8601 (YYYY-MM-DD)
+    pub fn set_date(mut self, value: &str) -> Self {
+        self.date = Some(value.to_string());
+        self
+    }
+    /// Set the URL of the license of the document
+    pub fn set_license(mut self, value: &str) -> Self {
+        self.license = Some(value.to_string());
+        self
+    }
+    pub fn set_description(mut self, value: &str) -> Self {
+        self.description = Some(value.to_string());
+        self
+    }
+    pub fn add_keyword(mut self, value: &str) -> Self {
+        self.keywords.push(value.to_string());
+        self
+    }
+    /// Set the URL of the resource the document is derived from
+    pub fn set_source(mut self, value: &str) -> Self {
+        self.source = Some(value.to_string());
+        self
+    }
+}
+
+impl fmt::Display for Metadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keywords = if self.keywords.is_empty() {
+            None
+        } else {
+            Some(format!(
+                "<dc:subject><rdf:Bag>{}</rdf:Bag></dc:subject>",
+                self.keywords
+                    .iter()
+                    .map(|x| format!("<rdf:li>{}</rdf:li>", escape_xml(x)))
+                    .collect::<Vec<String>>()
+                    .join("")
+            ))
+        };
+        let properties = vec![
+            Some("<dc:format>image/svg+xml</dc:format>".to_string()),
+            Some("<dc:type rdf:resource=\"http://purl.org/dc/dcmitype/StillImage\"/>".to_string()),
+            self.title
+                .as_ref()
+                .map(|x| format!("<dc:title>{}</dc:title>", escape_xml(x))),
+            self.creator.as_ref().map(|x| {
+                format!(
+                    "<dc:creator><cc:Agent><dc:title>{}</dc:title></cc:Agent></dc:creator>",
+                    escape_xml(x)
+                )
+            }),
+            self.date
+                .as_ref()
+                .map(|x| format!("<dc:date>{}</dc:date>", escape_xml(x))),
+            self.description
+                .as_ref()
+                .map(|x| format!("<dc:description>{}</dc:description>", escape_xml(x))),
+            keywords,
+            self.source
+                .as_ref()
+                .map(|x| format!("<dc:source>{}</dc:source>", escape_xml(x))),
+            self.license
+                .as_ref()
+                .map(|x| format!("<cc:license rdf:resource=\"{}\"/>", escape_xml(x))),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<String>>()
+        .join("\n");
+        write!(
+            f,
+            "<metadata>\n<rdf:RDF {} {} {}>\n<cc:Work rdf:about=\"\">\n{}\n</cc:Work>\n</rdf:RDF>\n</metadata>",
+            Namespace::rdf(),
+            Namespace::dc(),
+            Namespace::cc(),
+            properties
+        )
+    }
+}
+
+/// Escapes a string so it can be used inside of an XML comment
+pub(crate) fn escape_comment(value: &str) -> String {
+    let mut escaped = value.to_string();
+    while escaped.contains("--") {
+        escaped = escaped.replace("--", "- -");
+    }
+    if escaped.ends_with('-') {
+        escaped.push(' ');
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_render_metadata() {
+        let metadata = Metadata::new()
+            .set_creator("Jane & John")
+            .set_license("https://creativecommons.org/licenses/by/4.0/")
+            .add_keyword("art")
+            .add_keyword("svg");
+        assert_eq!(
+            metadata.to_string(),
+            "<metadata>\n\
+             <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\" \
+             xmlns:dc=\"http://purl.org/dc/elements/1.1/\" \
+             xmlns:cc=\"http://creativecommons.org/ns#\">\n\
+             <cc:Work rdf:about=\"\">\n\
+             <dc:format>image/svg+xml</dc:format>\n\
+             <dc:type rdf:resource=\"http://purl.org/dc/dcmitype/StillImage\"/>\n\
+             <dc:creator><cc:Agent><dc:title>Jane &amp; John</dc:title></cc:Agent></dc:creator>\n\
+             <dc:subject><rdf:Bag><rdf:li>art</rdf:li><rdf:li>svg</rdf:li></rdf:Bag></dc:subject>\n\
+             <cc:license rdf:resource=\"https://creativecommons.org/licenses/by/4.0/\"/>\n\
+             </cc:Work>\n</rdf:RDF>\n</metadata>"
+        );
+    }
+
+    #[test]
+    fn should_escape_comments() {
+        assert_eq!(escape_comment("a --- b-"), "a - - - b- ");
+    }
+}
@@ -0,0 +1,73 @@
+use crate::element::attributes::{escape_xml, AttributeName};
+use std::fmt;
+
+/// Prefixes which are bound without being declared on the root element:
+/// `xml` and `xmlns` are reserved by XML, `xlink` is always declared by `Document`
+pub(crate) static IMPLICIT_PREFIXES: [&str; 3] = ["xml", "xmlns", "xlink"];
+
+/// An XML namespace declared on the root of a document, making `prefix:name` attributes
+/// and elements available
+///
+/// # Examples
+///
+/// ```
+/// use svg_composer::namespace::Namespace;
+///
+/// let inkscape = Namespace::inkscape();
+/// let custom = Namespace::new("my", "https://example.com/ns/my").unwrap();
+/// assert_eq!(custom.to_string(), "xmlns:my=\"https://example.com/ns/my\"");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Namespace {
+    prefix: String,
+    uri: String,
+}
+
+impl Namespace {
+    pub fn new(prefix: &str, uri: &str) -> Result<Self, String> {
+        let name = AttributeName::from_string(prefix.to_string())?;
+        if name.prefix().is_some() {
+            return Err("Namespace prefixes cannot contain colons".to_string());
+        }
+        if IMPLICIT_PREFIXES.contains(&prefix) {
+            return Err(format!("The {} prefix cannot be redeclared", prefix));
+        }
+        Ok(Namespace {
+            prefix: prefix.to_string(),
+            uri: uri.to_string(),
+        })
+    }
+    pub fn inkscape() -> Self {
+        Self::new("inkscape", "http://www.inkscape.org/namespaces/inkscape").unwrap()
+    }
+    pub fn sodipodi() -> Self {
+        Self::new(
+            "sodipodi",
+            "http://sodipodi.sourceforge.net/DTD/sodipodi-0.dtd",
+        )
+        .unwrap()
+    }
+    pub fn rdf() -> Self {
+        Self::new("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#").unwrap()
+    }
+    /// Dublin Core elements
+    pub fn dc() -> Self {
+        Self::new("dc", "http://purl.org/dc/elements/1.1/").unwrap()
+    }
+    /// Creative Commons rights expression
+    pub fn cc() -> Self {
+        Self::new("cc", "http://creativecommons.org/ns#").unwrap()
+    }
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+}
+
+impl fmt::Display for Namespace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "xmlns:{}=\"{}\"", self.prefix, escape_xml(&self.uri))
+    }
+}
@@ -0,0 +1,308 @@
+use crate::element::attributes::{
+    ClassName, ElementId, Paint, Size, StrokeLineCap, TagName, ToSize,
+};
+use std::fmt;
+
+/// Selects the elements a style rule applies to
+/// https://developer.mozilla.org/en-US/docs/Web/CSS/CSS_Selectors
+#[derive(Clone)]
+pub enum Selector {
+    /// `*`, all elements
+    Universal,
+    /// All elements with the given tag name, e.g. `circle`
+    Element(TagName),
+    /// The element with the given id, e.g. `#my_path`
+    Id(ElementId),
+    /// All elements with the given class, e.g. `.highlight`
+    Class(ClassName),
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Selector::Universal => write!(f, "*"),
+            Selector::Element(tag_name) => write!(f, "{}", escape_identifier(tag_name.as_str())),
+            Selector::Id(id) => write!(f, "#{}", escape_identifier(id.as_str())),
+            Selector::Class(class_name) => write!(f, ".{}", class_name),
+        }
+    }
+}
+
+/// Escapes the characters of an XML name which are not allowed in a CSS identifier, like the
+/// dot which would otherwise select a class
+fn escape_identifier(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for c in name.chars() {
+        if !(c.is_alphanumeric() || c == '-' || c == '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// A list of typed CSS presentation properties, used by style rules and inline styles
+///
+/// # Examples
+///
+/// ```
+/// use svg_composer::element::attributes::{Color, ColorName, Paint};
+/// use svg_composer::stylesheet::StyleDeclarations;
+///
+/// let declarations = StyleDeclarations::new()
+///     .set_fill(Paint::from_color(Color::from_name(ColorName::Red)))
+///     .set_stroke_width(2.);
+/// assert_eq!(declarations.to_string(), "fill: red; stroke-width: 2px");
+/// ```
+#[derive(Clone, Default)]
+pub struct StyleDeclarations {
+    fill: Option<Paint>,
+    fill_opacity: Option<f64>,
+    stroke: Option<Paint>,
+    stroke_width: Option<Size>,
+    stroke_linecap: Option<StrokeLineCap>,
+    stroke_opacity: Option<f64>,
+    opacity: Option<f64>,
+    font_family: Option<String>,
+    font_size: Option<Size>,
+    properties: Vec<(String, String)>,
+}
+
+impl StyleDeclarations {
+    pub fn new() -> Self {
+        StyleDeclarations::default()
+    }
+    pub fn set_fill(mut self, value: Paint) -> Self {
+        self.fill = Some(value);
+        self
+    }
+    pub fn set_fill_opacity(mut self, value: f64) -> Self {
+        self.fill_opacity = Some(value);
+        self
+    }
+    pub fn set_stroke(mut self, value: Paint) -> Self {
+        self.stroke = Some(value);
+        self
+    }
+    pub fn set_stroke_width<I>(mut self, value: I) -> Self
+    where
+        I: ToSize,
+    {
+        self.stroke_width = Some(value.to_size());
+        self
+    }
+    pub fn set_stroke_linecap(mut self, value: StrokeLineCap) -> Self {
+        self.stroke_linecap = Some(value);
+        self
+    }
+    pub fn set_stroke_opacity(mut self, value: f64) -> Self {
+        self.stroke_opacity = Some(value);
+        self
+    }
+    pub fn set_opacity(mut self, value: f64) -> Self {
+        self.opacity = Some(value);
+        self
+    }
+    pub fn set_font_family(mut self, value: &str) -> Self {
+        self.font_family = Some(value.to_string());
+        self
+    }
+    pub fn set_font_size<I>(mut self, value: I) -> Self
+    where
+        I: ToSize,
+    {
+        self.font_size = Some(value.to_size());
+        self
+    }
+    /// Set a property which has no typed setter, replacing any previous value of the property.
+    /// Fails if the name is not made of letters, digits and hyphens, or if the value contains
+    /// a brace or a semicolon which would end the declaration.
+    pub fn set_property(mut self, name: &str, value: &str) -> Result<Self, String> {
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(format!("Invalid property name \"{}\"", name));
+        }
+        if value.contains(['{', '}', ';']) {
+            return Err(format!("Invalid value for property \"{}\"", name));
+        }
+        match self.properties.iter_mut().find(|(n, _)| n == name) {
+            Some(property) => property.1 = value.to_string(),
+            None => self.properties.push((name.to_string(), value.to_string())),
+        }
+        Ok(self)
+    }
+    /// Returns the value of a property which has no typed setter
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+    pub fn is_empty(&self) -> bool {
+        self.to_string().is_empty()
+    }
+    pub fn stroke(&self) -> Option<&Paint> {
+        self.stroke.as_ref()
+    }
+    pub fn stroke_width(&self) -> Option<Size> {
+        self.stroke_width
+    }
+    pub fn font_size(&self) -> Option<Size> {
+        self.font_size
+    }
+    /// Replaces the values of the properties which have no typed setter
+    pub(crate) fn map_property_values<F: Fn(&str) -> String>(&mut self, f: F) {
+        for property in self.properties.iter_mut() {
+            property.1 = f(&property.1);
+        }
+    }
+}
+
+impl fmt::Display for StyleDeclarations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let formatted_declarations = vec![
+            self.fill.as_ref().map(|x| format!("fill: {}", x)),
+            self.fill_opacity
+                .as_ref()
+                .map(|x| format!("fill-opacity: {}", x)),
+            self.stroke.as_ref().map(|x| format!("stroke: {}", x)),
+            self.stroke_width
+                .as_ref()
+                .map(|x| format!("stroke-width: {}", x.to_css())),
+            self.stroke_linecap
+                .as_ref()
+                .map(|x| format!("stroke-linecap: {}", x)),
+            self.stroke_opacity
+                .as_ref()
+                .map(|x| format!("stroke-opacity: {}", x)),
+            self.opacity.as_ref().map(|x| format!("opacity: {}", x)),
+            self.font_family
+                .as_ref()
+                .map(|x| format!("font-family: {}", x)),
+            self.font_size
+                .as_ref()
+                .map(|x| format!("font-size: {}", x.to_css())),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(
+            self.properties
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, value)),
+        )
+        .collect::<Vec<String>>()
+        .join("; ");
+        write!(f, "{}", formatted_declarations)
+    }
+}
+
+/// A CSS stylesheet rendered as a `<style>` element at the top of a document
+///
+/// # Examples
+///
+/// ```
+/// use svg_composer::document::Document;
+/// use svg_composer::element::attributes::{ClassName, Color, ColorName, Paint};
+/// use svg_composer::element::Element;
+/// use svg_composer::stylesheet::{Selector, StyleDeclarations, Stylesheet};
+///
+/// let highlight = ClassName::from_string("highlight".to_string()).unwrap();
+/// let mut document = Document::new(Vec::<Box<dyn Element>>::new(), None);
+/// document.stylesheet = Some(Stylesheet::new().add_rule(
+///     Selector::Class(highlight),
+///     StyleDeclarations::new().set_stroke(Paint::from_color(Color::from_name(ColorName::Red))),
+/// ));
+/// ```
+#[derive(Clone, Default)]
+pub struct Stylesheet {
+    rules: Vec<(Selector, StyleDeclarations)>,
+}
+
+impl Stylesheet {
+    pub fn new() -> Self {
+        Stylesheet::default()
+    }
+    pub fn add_rule(mut self, selector: Selector, declarations: StyleDeclarations) -> Self {
+        self.rules.push((selector, declarations));
+        self
+    }
+    pub(crate) fn rules_mut(&mut self) -> &mut Vec<(Selector, StyleDeclarations)> {
+        &mut self.rules
+    }
+}
+
+impl fmt::Display for Stylesheet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rules = self
+            .rules
+            .iter()
+            .map(|(selector, declarations)| {
+                if declarations.is_empty() {
+                    format!("{} {{ }}", selector)
+                } else {
+                    format!("{} {{ {}; }}", selector, declarations)
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        // The end of a CDATA section cannot appear inside of it, it is split across two sections
+        write!(
+            f,
+            "<style type=\"text/css\"><![CDATA[\n{}\n]]></style>",
+            rules.replace("]]>", "]]]]><![CDATA[>")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::attributes::{Color, ColorName};
+
+    #[test]
+    fn should_render_stylesheet() {
+        let stylesheet = Stylesheet::new()
+            .add_rule(
+                Selector::Class(ClassName::from_string("highlight".to_string()).unwrap()),
+                StyleDeclarations::new()
+                    .set_stroke(Paint::from_color(Color::from_name(ColorName::Red)))
+                    .set_stroke_linecap(StrokeLineCap::Round),
+            )
+            .add_rule(
+                Selector::Element(TagName::from_string("text".to_string()).unwrap()),
+                StyleDeclarations::new()
+                    .set_font_size(Size::from_percentage(120.))
+                    .set_font_family("\"a]]>b\""),
+            );
+        assert_eq!(
+            stylesheet.to_string(),
+            "<style type=\"text/css\"><![CDATA[\n\
+             .highlight { stroke: red; stroke-linecap: round; }\n\
+             text { font-family: \"a]]]]><![CDATA[>b\"; font-size: 120%; }\n\
+             ]]></style>"
+        );
+    }
+
+    #[test]
+    fn should_reject_rules_escaping_declarations() {
+        assert!(StyleDeclarations::new()
+            .set_property("fill", "red} svg{display:none")
+            .is_err());
+        assert!(StyleDeclarations::new()
+            .set_property("fill;stroke", "red")
+            .is_err());
+        assert!(StyleDeclarations::new().set_property("", "red").is_err());
+        assert!(TagName::from_string("a,*".to_string()).is_err());
+        assert!(ElementId::from_string("a,*".to_string()).is_err());
+
+        let stylesheet = Stylesheet::new().add_rule(
+            Selector::Id(ElementId::from_string("a.b".to_string()).unwrap()),
+            StyleDeclarations::new()
+                .set_property("--accent", "url(\"#a\")")
+                .unwrap(),
+        );
+        assert_eq!(
+            stylesheet.to_string(),
+            "<style type=\"text/css\"><![CDATA[\n#a\\.b { --accent: url(\"#a\"); }\n]]></style>"
+        );
+    }
+}
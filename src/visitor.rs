@@ -0,0 +1,145 @@
+use crate::element::path::Commands;
+use crate::element::Element;
+
+/// A pass reading the elements of a document, see `Document::accept`.
+/// All methods do nothing by default so that visitors only implement the ones they need.
+///
+/// # Examples
+///
+/// ```
+/// use svg_composer::document::Document;
+/// use svg_composer::element::attributes::{Color, ColorName, Paint};
+/// use svg_composer::element::circle::Circle;
+/// use svg_composer::element::Element;
+/// use svg_composer::visitor::Visitor;
+///
+/// struct StrokeCollector(Vec<String>);
+///
+/// impl Visitor for StrokeCollector {
+///     fn visit_element(&mut self, element: &dyn Element) {
+///         if let Some(stroke) = element.get_attributes().stroke {
+///             self.0.push(stroke.to_string());
+///         }
+///     }
+/// }
+///
+/// let document = Document::new(
+///     vec![Box::new(Circle::new().set_stroke(Paint::from_color(Color::from_name(ColorName::Red))))],
+///     None,
+/// );
+/// let mut collector = StrokeCollector(Vec::new());
+/// document.accept(&mut collector);
+/// assert_eq!(collector.0, vec!["red"]);
+/// ```
+pub trait Visitor {
+    /// Called for every element, before its path commands and its children
+    fn visit_element(&mut self, _element: &dyn Element) {}
+    /// Called with the path commands (`d` attribute) of elements which have some
+    fn visit_commands(&mut self, _commands: &Commands) {}
+    /// Called for every element, after all of its children were visited
+    fn leave_element(&mut self, _element: &dyn Element) {}
+}
+
+/// A pass modifying the elements of a document in place, see `Document::accept_mut`.
+/// All methods do nothing by default so that visitors only implement the ones they need.
+pub trait VisitorMut {
+    /// Called for every element, before its path commands and its children
+    fn visit_element(&mut self, _element: &mut dyn Element) {}
+    /// Called with the path commands (`d` attribute) of elements which have some
+    fn visit_commands(&mut self, _commands: &mut Commands) {}
+    /// Called for every element, after all of its children were visited
+    fn leave_element(&mut self, _element: &mut dyn Element) {}
+}
+
+/// Visits an element and its descendants in depth-first order
+pub fn walk<V: Visitor + ?Sized>(visitor: &mut V, element: &dyn Element) {
+    visitor.visit_element(element);
+    if let Some(commands) = element.get_attributes().d.as_ref() {
+        visitor.visit_commands(commands);
+    }
+    if let Some(children) = element.get_children() {
+        for child in children.iter() {
+            walk(visitor, child.as_ref());
+        }
+    }
+    visitor.leave_element(element);
+}
+
+/// Visits an element and its descendants in depth-first order, allowing modifications
+pub fn walk_mut<V: VisitorMut + ?Sized>(visitor: &mut V, element: &mut dyn Element) {
+    visitor.visit_element(element);
+    if let Some(commands) = element.get_mut_attributes().d.as_mut() {
+        visitor.visit_commands(commands);
+    }
+    if let Some(children) = element.get_mut_children() {
+        for child in children.iter_mut() {
+            walk_mut(visitor, child.as_mut());
+        }
+    }
+    visitor.leave_element(element);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::Document;
+    use crate::element::attributes::{ElementId, Size};
+    use crate::element::circle::Circle;
+    use crate::element::group::Group;
+    use crate::element::layer::Layer;
+
+    struct IdPrefixer(&'static str);
+
+    impl VisitorMut for IdPrefixer {
+        fn visit_element(&mut self, element: &mut dyn Element) {
+            let attributes = element.get_mut_attributes();
+            if let Some(id) = attributes.id.as_ref() {
+                attributes.id = ElementId::from_string(format!("{}{}", self.0, id)).ok();
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct DepthTracker {
+        depth: usize,
+        max_depth: usize,
+    }
+
+    impl Visitor for DepthTracker {
+        fn visit_element(&mut self, _element: &dyn Element) {
+            self.depth += 1;
+            self.max_depth = self.max_depth.max(self.depth);
+        }
+        fn leave_element(&mut self, _element: &dyn Element) {
+            self.depth -= 1;
+        }
+    }
+
+    #[test]
+    fn should_visit_nested_elements() {
+        let mut document = Document::new(
+            vec![
+                Box::new(Circle::new().set_id(ElementId::from_string("a".to_string()).unwrap())),
+                Box::new(
+                    Group::new().add_element(Box::new(Group::new().add_element(Box::new(
+                        Circle::new().set_id(ElementId::from_string("b".to_string()).unwrap()),
+                    )))),
+                ),
+            ],
+            None,
+        );
+        document
+            .add_layer(Layer::new("layer").add_element(Box::new(
+                Circle::new().set_stroke_width(Size::from_length(1.)),
+            )))
+            .unwrap();
+
+        let mut tracker = DepthTracker::default();
+        document.accept(&mut tracker);
+        assert_eq!((tracker.depth, tracker.max_depth), (0, 3));
+
+        document.accept_mut(&mut IdPrefixer("panel_1_"));
+        assert!(document.get_by_id("panel_1_a").is_some());
+        assert!(document.get_by_id("panel_1_b").is_some());
+    }
+}